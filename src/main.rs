@@ -1,6 +1,8 @@
 mod app;
 mod modules;
 
+use std::process;
+
 fn main() {
 	let (app, module_manager) = app::build_app();
 
@@ -11,7 +13,10 @@ fn main() {
 	let (name, matches) = matches.subcommand();
 
 	if let Some(matches) = matches {
-		module_manager.run(name, matches);
+		if let Err(e) = module_manager.run(name, matches) {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
 	} else {
 		app_clone.print_help().unwrap_or(());
 		println!();
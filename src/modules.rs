@@ -10,21 +10,33 @@
 
 use clap::{App, ArgMatches};
 use linked_hash_map::LinkedHashMap;
+use std::io::{self, Write};
 use std::iter;
+mod address;
 mod aes;
 mod base;
 mod base58;
 mod base64;
+mod block_mode;
 mod case;
+mod chacha;
+mod codec;
 mod completion;
 mod ecdsa;
 mod eddsa;
 mod hash;
+mod hd;
 mod hex;
 mod html;
+mod jwt;
+mod kdf;
+mod key;
+mod mnemonic;
 mod number_codec;
 mod number_system;
+mod password;
 mod pbkdf2;
+mod pipe;
 mod re;
 mod sm4;
 mod srdsa;
@@ -72,6 +84,7 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 		mm.register(number_system::module());
 		mm.register(base58::module());
 		mm.register(base64::module());
+		mm.register(codec::module());
 		mm.register(url::module());
 		mm.register(number_codec::module());
 		mm.register(hash::module());
@@ -79,12 +92,20 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 		mm.register(html::module());
 		mm.register(re::module());
 		mm.register(pbkdf2::module());
+		mm.register(kdf::module());
+		mm.register(password::module());
 		mm.register(case::module());
 		mm.register(aes::module());
 		mm.register(ecdsa::module());
 		mm.register(sm4::module());
 		mm.register(eddsa::module());
 		mm.register(srdsa::module());
+		mm.register(key::module());
+		mm.register(address::module());
+		mm.register(mnemonic::module());
+		mm.register(hd::module());
+		mm.register(jwt::module());
+		mm.register(chacha::module());
 		mm
 	}
 
@@ -93,20 +114,36 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 			.iter()
 			.map(|(_, command)| command.app.to_owned())
 			.chain(iter::once(usage::app()))
+			.chain(iter::once(pipe::app()))
 			.chain(iter::once(completion::app()))
 			.collect()
 	}
 
-	pub fn run(&self, name: &str, matches: &ArgMatches<'a>) {
+	pub fn run(&self, name: &str, matches: &ArgMatches<'a>) -> Result<(), String> {
 		let result = match name {
 			"usage" => usage::run(matches, &self.modules),
+			"pipe" => pipe::run(matches, &self.commands),
 			"completion" => completion::run(matches),
+			"hash" if matches.is_present("CHECK") => {
+				// like sha256sum, a failed check prints its report and then fails with the warning
+				let (result, warning) = hash::check(matches)?;
+				result.iter().for_each(|x| println!("{}", x));
+				return warning.map_or(Ok(()), Err);
+			}
 			_ => (self.commands.get(name).expect("subcommand must exist").f)(matches),
 		};
 
-		match result {
-			Ok(result) => result.iter().for_each(|x| println!("{}", x)),
-			Err(e) => eprintln!("{}", e),
+		match (result, matches.value_of("OUT_FORMAT")) {
+			(Ok(result), Some(format)) => format
+				.parse::<base::Format>()
+				.and_then(|format| base::output_bytes(result, format))
+				.and_then(|result| {
+					io::stdout()
+						.write_all(&result)
+						.map_err(|_| "Output failed".to_string())
+				}),
+			(Ok(result), None) => Ok(result.iter().for_each(|x| println!("{}", x))),
+			(Err(e), _) => Err(e),
 		}
 	}
 
@@ -0,0 +1,550 @@
+use crate::modules::base::Hex;
+use crate::modules::base58::{self, Bech32Variant};
+use crate::modules::{base, hash, Command, Module};
+use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use secp256k1::{PublicKey, Secp256k1};
+use std::collections::HashMap;
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Blockchain address".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+struct Chain {
+	name: &'static str,
+	help: &'static str,
+	kind: ChainKind,
+}
+
+enum ChainKind {
+	BitcoinP2pkh,
+	BitcoinP2wpkh,
+	BitcoinP2tr,
+	Ethereum,
+	Substrate,
+	Solana,
+}
+
+lazy_static! {
+	static ref RAW_CHAINS: Vec<Chain> = vec![
+		Chain {
+			name: "bitcoin_p2pkh",
+			help: "Bitcoin P2PKH (secp256k1 public key)",
+			kind: ChainKind::BitcoinP2pkh,
+		},
+		Chain {
+			name: "bitcoin_p2wpkh",
+			help: "Bitcoin P2WPKH (secp256k1 public key)",
+			kind: ChainKind::BitcoinP2wpkh,
+		},
+		Chain {
+			name: "bitcoin_p2tr",
+			help: "Bitcoin P2TR, BIP-86 key path (secp256k1 public key)",
+			kind: ChainKind::BitcoinP2tr,
+		},
+		Chain {
+			name: "ethereum",
+			help: "Ethereum, EIP-55 checksum (secp256k1 public key)",
+			kind: ChainKind::Ethereum,
+		},
+		Chain {
+			name: "substrate",
+			help: "Substrate SS58 (sr25519 / ed25519 public key)",
+			kind: ChainKind::Substrate,
+		},
+		Chain {
+			name: "solana",
+			help: "Solana (ed25519 public key)",
+			kind: ChainKind::Solana,
+		},
+	];
+	static ref CHAINS: HashMap<&'static str, &'static Chain> =
+		RAW_CHAINS.iter().map(|x| (x.name, x)).collect();
+	static ref CHAIN_NAMES: Vec<&'static str> = RAW_CHAINS.iter().map(|x| x.name).collect();
+	static ref CHAIN_HELP: String = "Chain type\n".to_string()
+		+ &RAW_CHAINS
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+}
+
+/// Version byte of Bitcoin mainnet P2PKH
+const BITCOIN_P2PKH_VERSION: u8 = 0x00;
+
+/// Human-readable part of Bitcoin mainnet SegWit addresses
+const BITCOIN_HRP: &str = "bc";
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("addr")
+				.about("Convert public key to address")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Public key (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("TYPE")
+						.long("type")
+						.short("t")
+						.help(&CHAIN_HELP)
+						.takes_value(true)
+						.possible_values(&CHAIN_NAMES)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.short("p")
+						.help("Network prefix (substrate)\n0: Polkadot\n2: Kusama\n42: Substrate\netc\n")
+						.takes_value(true)
+						.default_value("42")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: addr,
+		},
+		Command {
+			app: SubCommand::with_name("addr_decode")
+				.about("Validate and decode address (Public key hash / Output key / Public key)")
+				.arg(Arg::with_name("INPUT").help("Address").required(false).index(1))
+				.arg(
+					Arg::with_name("TYPE")
+						.long("type")
+						.short("t")
+						.help(&CHAIN_HELP)
+						.takes_value(true)
+						.possible_values(&CHAIN_NAMES)
+						.required(true),
+				),
+			f: addr_decode,
+		},
+	]
+}
+
+fn addr(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let public_key: Vec<u8> = input
+		.parse::<Hex>()
+		.map_err(|_| "Invalid public key")?
+		.into();
+
+	let chain = get_chain(matches)?;
+
+	let result = match chain.kind {
+		ChainKind::BitcoinP2pkh => {
+			secp256k1_public_key(&public_key)?;
+			let data = [&[BITCOIN_P2PKH_VERSION][..], &hash::hash160(&public_key)].concat();
+			bs58::encode(data).with_check().into_string()
+		}
+		ChainKind::BitcoinP2wpkh => {
+			// BIP-143 only allows compressed public keys
+			let public_key = secp256k1_public_key(&public_key)?.serialize();
+			let program = hash::hash160(&public_key);
+			base58::segwit_encode(BITCOIN_HRP, 0, &program, Bech32Variant::Bech32)?
+		}
+		ChainKind::BitcoinP2tr => {
+			let output_key = taproot_output_key(&public_key)?;
+			base58::segwit_encode(BITCOIN_HRP, 1, &output_key, Bech32Variant::Bech32m)?
+		}
+		ChainKind::Ethereum => {
+			let public_key = secp256k1_public_key(&public_key)?.serialize_uncompressed();
+			let address = &hash::sha3_k_256_hash(&public_key[1..])[12..];
+			eip55_checksum(address)
+		}
+		ChainKind::Substrate => {
+			let prefix = matches.value_of("PREFIX").ok_or("Invalid prefix")?;
+			let prefix = prefix.parse::<u16>().map_err(|_| "Invalid prefix")?;
+			base58::ss58_encode(prefix, &public_key)?
+		}
+		ChainKind::Solana => {
+			if public_key.len() != 32 {
+				return Err("Invalid public key".to_string());
+			}
+			bs58::encode(public_key).into_string()
+		}
+	};
+
+	Ok(vec![result])
+}
+
+fn addr_decode(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input = input.trim();
+
+	let chain = get_chain(matches)?;
+
+	let result = match chain.kind {
+		ChainKind::BitcoinP2pkh => {
+			let data = bs58::decode(input)
+				.with_check(Some(BITCOIN_P2PKH_VERSION))
+				.into_vec()
+				.map_err(|_| "Invalid address")?;
+			if data.len() != 21 {
+				return Err("Invalid address: length".to_string());
+			}
+			Hex::from(data[1..].to_vec()).into()
+		}
+		ChainKind::BitcoinP2wpkh | ChainKind::BitcoinP2tr => {
+			let (hrp, version, program) = base58::segwit_decode(input)?;
+			if hrp != BITCOIN_HRP {
+				return Err(format!("Invalid address: hrp should be {}", BITCOIN_HRP));
+			}
+			let expected = match chain.kind {
+				ChainKind::BitcoinP2wpkh => (0, 20),
+				_ => (1, 32),
+			};
+			if (version, program.len()) != expected {
+				return Err(format!(
+					"Invalid address: witness version {} and program length {} expected",
+					expected.0, expected.1
+				));
+			}
+			Hex::from(program).into()
+		}
+		ChainKind::Ethereum => {
+			let address = input
+				.strip_prefix("0x")
+				.ok_or("Invalid address: 0x prefix expected")?;
+			if address.len() != 40 {
+				return Err("Invalid address: length".to_string());
+			}
+			let data = hex::decode(address).map_err(|_| "Invalid address")?;
+			// all lower or all upper case addresses carry no checksum
+			let checksummed = address.chars().any(|c| c.is_ascii_lowercase())
+				&& address.chars().any(|c| c.is_ascii_uppercase());
+			if checksummed && eip55_checksum(&data) != input {
+				return Err("Invalid address: checksum".to_string());
+			}
+			Hex::from(data).into()
+		}
+		ChainKind::Substrate => {
+			let (prefix, public_key) = base58::ss58_decode(input)?;
+			let public_key: String = Hex::from(public_key).into();
+			format!("({}, {})", prefix, public_key)
+		}
+		ChainKind::Solana => {
+			let data = bs58::decode(input)
+				.into_vec()
+				.map_err(|_| "Invalid address")?;
+			if data.len() != 32 {
+				return Err("Invalid address: length".to_string());
+			}
+			Hex::from(data).into()
+		}
+	};
+
+	Ok(vec![result])
+}
+
+fn get_chain(matches: &ArgMatches) -> Result<&'static Chain, String> {
+	let chain = matches.value_of("TYPE").ok_or("Invalid type")?;
+	let chain = CHAINS.get(chain).ok_or("Invalid type")?;
+	Ok(chain)
+}
+
+fn secp256k1_public_key(public_key: &[u8]) -> Result<PublicKey, String> {
+	PublicKey::from_slice(public_key).map_err(|_| "Invalid public key".to_string())
+}
+
+/// BIP-86: tweak the internal key without a script path (BIP-341)
+fn taproot_output_key(public_key: &[u8]) -> Result<Vec<u8>, String> {
+	// x-only public key (BIP-340) is lifted to the point with even y
+	let x_only = match public_key.len() {
+		32 => public_key.to_vec(),
+		_ => secp256k1_public_key(public_key)?.serialize()[1..].to_vec(),
+	};
+	let mut internal_key = secp256k1_public_key(&[&[0x02][..], &x_only].concat())?;
+
+	let tag = hash::sha2_256_hash(b"TapTweak");
+	let tweak = hash::sha2_256_hash(&[&tag[..], &tag, &x_only].concat());
+
+	let secp = Secp256k1::verification_only();
+	internal_key
+		.add_exp_assign(&secp, &tweak)
+		.map_err(|_| "Invalid public key")?;
+
+	Ok(internal_key.serialize()[1..].to_vec())
+}
+
+/// EIP-55: upper case the letters whose nibble in keccak256(lower case hex) is >= 8
+fn eip55_checksum(address: &[u8]) -> String {
+	let address = hex::encode(address);
+	let hash = hash::sha3_k_256_hash(address.as_bytes());
+	let address = address
+		.chars()
+		.enumerate()
+		.map(|(i, c)| {
+			let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+			match nibble >= 8 {
+				true => c.to_ascii_uppercase(),
+				false => c,
+			}
+		})
+		.collect::<String>();
+	format!("0x{}", address)
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"addr",
+				vec![
+					Case {
+						desc: "Bitcoin P2PKH".to_string(),
+						input: vec![
+							"-t",
+							"bitcoin_p2pkh",
+							"0x0250863ad64a87ae8a2fe83c1af1a8403cb53f53e486d8511dad8a04887e5b2352",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bitcoin P2WPKH".to_string(),
+						input: vec![
+							"-t",
+							"bitcoin_p2wpkh",
+							"0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bitcoin P2TR (BIP-86)".to_string(),
+						input: vec![
+							"-t",
+							"bitcoin_p2tr",
+							"0x02cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Ethereum".to_string(),
+						input: vec![
+							"-t",
+							"ethereum",
+							"0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Substrate (Polkadot)".to_string(),
+						input: vec![
+							"-t",
+							"substrate",
+							"-p",
+							"0",
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Solana".to_string(),
+						input: vec![
+							"-t",
+							"solana",
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["FHNpKmJrUtusuvKPGomAygQqeiks98bdV6yD61Stb6vg"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"addr_decode",
+				vec![
+					Case {
+						desc: "Bitcoin P2PKH".to_string(),
+						input: vec!["-t", "bitcoin_p2pkh", "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0xf54a5851e9372b87810a8e60cdd2e7cfd80b6e31"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bitcoin P2WPKH".to_string(),
+						input: vec![
+							"-t",
+							"bitcoin_p2wpkh",
+							"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x751e76e8199196d454941c45d1b3a323f1433bd6"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bitcoin P2TR".to_string(),
+						input: vec![
+							"-t",
+							"bitcoin_p2tr",
+							"bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xa60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Ethereum".to_string(),
+						input: vec![
+							"-t",
+							"ethereum",
+							"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Substrate".to_string(),
+						input: vec![
+							"-t",
+							"substrate",
+							"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(42, 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Solana".to_string(),
+						input: vec![
+							"-t",
+							"solana",
+							"FHNpKmJrUtusuvKPGomAygQqeiks98bdV6yD61Stb6vg",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
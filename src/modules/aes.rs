@@ -1,11 +1,14 @@
-use self::Mode::{CBC, CTR, ECB};
+use self::Mode::{CBC, CCM, CTR, ECB, GCM};
 use crate::modules::base::Hex;
+use crate::modules::block_mode::{self, Block, Padding};
 use crate::modules::{base, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
-use crypto::aes::{cbc_decryptor, cbc_encryptor, ctr, ecb_decryptor, ecb_encryptor, KeySize};
-use crypto::blockmodes::PkcsPadding;
-use crypto::buffer::{RefReadBuffer, RefWriteBuffer, WriteBuffer};
-use crypto::symmetriccipher::{Decryptor, Encryptor};
+use crypto::aes::KeySize;
+use crypto::aessafe::{
+	AesSafe128Decryptor, AesSafe128Encryptor, AesSafe192Decryptor, AesSafe192Encryptor,
+	AesSafe256Decryptor, AesSafe256Encryptor,
+};
+use crypto::symmetriccipher::{BlockDecryptor, BlockEncryptor};
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
@@ -30,9 +33,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("MODE")
 						.long("mode")
 						.short("m")
-						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\n")
+						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\ngcm: GCM\nccm: CCM\n")
 						.takes_value(true)
-						.possible_values(&["ecb", "cbc", "ctr"])
+						.possible_values(&["ecb", "cbc", "ctr", "gcm", "ccm"])
 						.required(true),
 				)
 				.arg(
@@ -50,7 +53,45 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("IV (Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("PADDING")
+						.long("padding")
+						.short("p")
+						.help(block_mode::PADDING_HELP)
+						.takes_value(true)
+						.possible_values(&block_mode::PADDING_NAMES)
+						.default_value("pkcs7")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce for gcm, ccm (Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for gcm, ccm (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TAG_LENGTH")
+						.long("tag-length")
+						.short("t")
+						.help("Tag length for gcm, ccm (bits, the tag is appended to the cipher)")
+						.takes_value(true)
+						.default_value("128")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: aes_enc,
 		},
 		Command {
@@ -66,9 +107,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("MODE")
 						.long("mode")
 						.short("m")
-						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\n")
+						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\ngcm: GCM\nccm: CCM\n")
 						.takes_value(true)
-						.possible_values(&["ecb", "cbc", "ctr"])
+						.possible_values(&["ecb", "cbc", "ctr", "gcm", "ccm"])
 						.required(true),
 				)
 				.arg(
@@ -86,16 +127,71 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("IV (Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("PADDING")
+						.long("padding")
+						.short("p")
+						.help(block_mode::PADDING_HELP)
+						.takes_value(true)
+						.possible_values(&block_mode::PADDING_NAMES)
+						.default_value("pkcs7")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce for gcm, ccm (Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for gcm, ccm (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TAG_LENGTH")
+						.long("tag-length")
+						.short("t")
+						.help("Tag length for gcm, ccm (bits, the tag is appended to the cipher)")
+						.takes_value(true)
+						.default_value("128")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: aes_dec,
 		},
 	]
 }
 
 enum Mode {
-	ECB,
-	CBC { iv: Vec<u8> },
-	CTR { iv: Vec<u8> },
+	ECB {
+		padding: Padding,
+	},
+	CBC {
+		iv: Vec<u8>,
+		padding: Padding,
+	},
+	CTR {
+		iv: Vec<u8>,
+	},
+	GCM {
+		nonce: Vec<u8>,
+		aad: Vec<u8>,
+		tag_len: usize,
+	},
+	CCM {
+		nonce: Vec<u8>,
+		aad: Vec<u8>,
+		tag_len: usize,
+	},
 }
 
 fn aes_enc(matches: &ArgMatches) -> Result<Vec<String>, String> {
@@ -103,9 +199,21 @@ fn aes_enc(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	// cipher
 	let result = match mode {
-		ECB => aes_enc_ecb(key_size, &key, &input),
-		CBC { iv } => aes_enc_cbc(key_size, &key, &input, &iv),
-		CTR { iv } => aes_enc_ctr(key_size, &key, &input, &iv),
+		ECB { padding } => block_mode::ecb_encrypt(aes_block_enc(key_size, &key), &input, padding),
+		CBC { iv, padding } => {
+			block_mode::cbc_encrypt(aes_block_enc(key_size, &key), &iv, &input, padding)
+		}
+		CTR { iv } => block_mode::ctr_process(aes_block_enc(key_size, &key), &iv, &input),
+		GCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::gcm_encrypt(aes_block_enc(key_size, &key), &nonce, &aad, &input, tag_len),
+		CCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::ccm_encrypt(aes_block_enc(key_size, &key), &nonce, &aad, &input, tag_len),
 	}?;
 	let result = Hex::from(result).into();
 
@@ -117,9 +225,21 @@ fn aes_dec(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	// plain
 	let result = match mode {
-		ECB => aes_dec_ecb(key_size, &key, &input),
-		CBC { iv } => aes_dec_cbc(key_size, &key, &input, &iv),
-		CTR { iv } => aes_dec_ctr(key_size, &key, &input, &iv),
+		ECB { padding } => block_mode::ecb_decrypt(aes_block_dec(key_size, &key), &input, padding),
+		CBC { iv, padding } => {
+			block_mode::cbc_decrypt(aes_block_dec(key_size, &key), &iv, &input, padding)
+		}
+		CTR { iv } => block_mode::ctr_process(aes_block_enc(key_size, &key), &iv, &input),
+		GCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::gcm_decrypt(aes_block_enc(key_size, &key), &nonce, &aad, &input, tag_len),
+		CCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::ccm_decrypt(aes_block_enc(key_size, &key), &nonce, &aad, &input, tag_len),
 	}?;
 	let result = Hex::from(result).into();
 
@@ -149,14 +269,65 @@ fn get_common_arg(matches: &ArgMatches) -> Result<(KeySize, Vec<u8>, Mode, Vec<u
 		Ok(iv)
 	};
 
+	let get_padding = || -> Result<Padding, String> {
+		let padding = matches
+			.value_of("PADDING")
+			.ok_or_else(|| "Invalid padding".to_string())?;
+		padding.parse::<Padding>()
+	};
+
+	let get_nonce = || -> Result<Vec<u8>, String> {
+		let nonce = matches
+			.value_of("NONCE")
+			.ok_or_else(|| "Invalid nonce".to_string())?;
+		let nonce: Vec<u8> = nonce.parse::<Hex>().map_err(|_| "Invalid nonce")?.into();
+		Ok(nonce)
+	};
+
+	let get_aad = || -> Result<Vec<u8>, String> {
+		let aad = matches
+			.value_of("AAD")
+			.ok_or_else(|| "Invalid AAD".to_string())?;
+		let aad: Vec<u8> = aad.parse::<Hex>().map_err(|_| "Invalid AAD")?.into();
+		Ok(aad)
+	};
+
+	let get_tag_len = || -> Result<usize, String> {
+		let tag_length = matches
+			.value_of("TAG_LENGTH")
+			.ok_or_else(|| "Invalid tag length".to_string())?;
+		let tag_length = tag_length
+			.parse::<usize>()
+			.map_err(|_| "Invalid tag length")?;
+		if tag_length % 8 != 0 {
+			return Err("Invalid tag length (must be a multiple of 8)".to_string());
+		}
+		Ok(tag_length / 8)
+	};
+
 	// mode
 	let mode = matches
 		.value_of("MODE")
 		.ok_or_else(|| "Invalid mode".to_string())?;
 	let mode = match mode {
-		"ecb" => ECB,
-		"cbc" => CBC { iv: get_iv()? },
+		"ecb" => ECB {
+			padding: get_padding()?,
+		},
+		"cbc" => CBC {
+			iv: get_iv()?,
+			padding: get_padding()?,
+		},
 		"ctr" => CTR { iv: get_iv()? },
+		"gcm" => GCM {
+			nonce: get_nonce()?,
+			aad: get_aad()?,
+			tag_len: get_tag_len()?,
+		},
+		"ccm" => CCM {
+			nonce: get_nonce()?,
+			aad: get_aad()?,
+			tag_len: get_tag_len()?,
+		},
 		_ => unreachable!(),
 	};
 
@@ -166,81 +337,32 @@ fn get_common_arg(matches: &ArgMatches) -> Result<(KeySize, Vec<u8>, Mode, Vec<u
 	Ok((key_size, key, mode, input))
 }
 
-fn aes_enc_ecb(key_size: KeySize, key: &[u8], input: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = ecb_encryptor(key_size, key, PkcsPadding);
-	let cipher_len = cipher_length(input.len());
-	let mut result = vec![0u8; cipher_len];
-	a.encrypt(
-		&mut RefReadBuffer::new(&input),
-		&mut RefWriteBuffer::new(&mut result),
-		true,
-	)
-	.map_err(|_| "Enc failed")?;
-	Ok(result)
-}
-
-fn aes_enc_cbc(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = cbc_encryptor(key_size, key, iv, PkcsPadding);
-	let cipher_len = cipher_length(input.len());
-	let mut result = vec![0u8; cipher_len];
-	a.encrypt(
-		&mut RefReadBuffer::new(&input),
-		&mut RefWriteBuffer::new(&mut result),
-		true,
-	)
-	.map_err(|_| "Enc failed")?;
-	Ok(result)
-}
-
-fn aes_enc_ctr(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = ctr(key_size, key, iv);
-	let mut result = vec![0u8; input.len()];
-	a.encrypt(
-		&mut RefReadBuffer::new(&input),
-		&mut RefWriteBuffer::new(&mut result),
-		true,
-	)
-	.map_err(|_| "Enc failed")?;
-	Ok(result)
-}
-
-fn aes_dec_ecb(key_size: KeySize, key: &[u8], input: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = ecb_decryptor(key_size, key, PkcsPadding);
-	let mut result = vec![0u8; input.len()];
-	let mut buffer = RefWriteBuffer::new(&mut result);
-	a.decrypt(&mut RefReadBuffer::new(&input), &mut buffer, true)
-		.map_err(|_| "Dec failed")?;
-	let len = buffer.capacity() - buffer.remaining();
-	let mut result = result.clone();
-	result.truncate(len);
-	Ok(result)
-}
-
-fn aes_dec_cbc(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = cbc_decryptor(key_size, key, iv, PkcsPadding);
-	let mut result = vec![0u8; input.len()];
-	let mut buffer = RefWriteBuffer::new(&mut result);
-	a.decrypt(&mut RefReadBuffer::new(&input), &mut buffer, true)
-		.map_err(|_| "Dec failed")?;
-	let len = buffer.capacity() - buffer.remaining();
-	let mut result = result.clone();
-	result.truncate(len);
-	Ok(result)
-}
-
-fn aes_dec_ctr(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	let mut a = ctr(key_size, key, iv);
-	let mut result = vec![0u8; input.len()];
-	let mut buffer = RefWriteBuffer::new(&mut result);
-	a.decrypt(&mut RefReadBuffer::new(&input), &mut buffer, true)
-		.map_err(|_| "Dec failed")?;
-	Ok(result)
+/// AES block encrypt function for the shared block modes
+fn aes_block_enc(key_size: KeySize, key: &[u8]) -> impl Fn(&Block) -> Block {
+	let encryptor: Box<dyn BlockEncryptor> = match key_size {
+		KeySize::KeySize128 => Box::new(AesSafe128Encryptor::new(key)),
+		KeySize::KeySize192 => Box::new(AesSafe192Encryptor::new(key)),
+		KeySize::KeySize256 => Box::new(AesSafe256Encryptor::new(key)),
+	};
+	move |block| {
+		let mut result = [0u8; block_mode::BLOCK_SIZE];
+		encryptor.encrypt_block(block, &mut result);
+		result
+	}
 }
 
-const BLOCK_SIZE: usize = 16;
-
-fn cipher_length(input_len: usize) -> usize {
-	((input_len / BLOCK_SIZE) + 1) * BLOCK_SIZE
+/// AES block decrypt function for the shared block modes
+fn aes_block_dec(key_size: KeySize, key: &[u8]) -> impl Fn(&Block) -> Block {
+	let decryptor: Box<dyn BlockDecryptor> = match key_size {
+		KeySize::KeySize128 => Box::new(AesSafe128Decryptor::new(key)),
+		KeySize::KeySize192 => Box::new(AesSafe192Decryptor::new(key)),
+		KeySize::KeySize256 => Box::new(AesSafe256Decryptor::new(key)),
+	};
+	move |block| {
+		let mut result = [0u8; block_mode::BLOCK_SIZE];
+		decryptor.decrypt_block(block, &mut result);
+		result
+	}
 }
 
 mod cases {
@@ -573,6 +695,200 @@ mod cases {
 						is_test: true,
 						since: "0.6.0".to_string(),
 					},
+					Case {
+						desc: "KeySize 128 ECB zero padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"zero",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0xeb1bd60dbb7df5586b0d70fdf23bb182"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 ECB ISO/IEC 7816-4 padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"iso7816",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0xcdf15841311e8c0212aa3ce513c6b864"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CBC ANSI X9.23 padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-i",
+							"03030303030303030303030303030303",
+							"-m",
+							"cbc",
+							"-p",
+							"x923",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0xdd8941a4c217f2dbb0f38ba16551fe3e"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 ECB no padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"none",
+							"0x61626364616263646162636461626364",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0xd7f480b25ee881f4b14d9893e6d76e7d"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 GCM (NIST test case 2)".to_string(),
+						input: vec![
+							"-k",
+							"00000000000000000000000000000000",
+							"-n",
+							"000000000000000000000000",
+							"-m",
+							"gcm",
+							"0x00000000000000000000000000000000",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 GCM with AAD (NIST test case 4)".to_string(),
+						input: vec![
+							"-k",
+							"feffe9928665731c6d6a8f9467308308",
+							"-n",
+							"cafebabefacedbaddecaf888",
+							"-a",
+							"0xfeedfacedeadbeeffeedfacedeadbeefabaddad2",
+							"-m",
+							"gcm",
+							"0xd9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e0915bc94fbc3221a5db94fae95ae7121a47",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CCM (RFC 3610 packet vector 1)".to_string(),
+						input: vec![
+							"-k",
+							"c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+							"-n",
+							"00000003020100a0a1a2a3a4a5",
+							"-a",
+							"0x0001020304050607",
+							"-t",
+							"64",
+							"-m",
+							"ccm",
+							"0x08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CCM (NIST SP 800-38C example 1)".to_string(),
+						input: vec![
+							"-k",
+							"404142434445464748494a4b4c4d4e4f",
+							"-n",
+							"10111213141516",
+							"-a",
+							"0x0001020304050607",
+							"-t",
+							"32",
+							"-m",
+							"ccm",
+							"0x20212223",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x7162015b4dac255d"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
 				],
 			),
 			(
@@ -907,6 +1223,184 @@ mod cases {
 						is_test: true,
 						since: "0.6.0".to_string(),
 					},
+					Case {
+						desc: "KeySize 128 ECB zero padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"zero",
+							"0xeb1bd60dbb7df5586b0d70fdf23bb182",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 ECB ISO/IEC 7816-4 padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"iso7816",
+							"0xcdf15841311e8c0212aa3ce513c6b864",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CBC ANSI X9.23 padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-i",
+							"03030303030303030303030303030303",
+							"-m",
+							"cbc",
+							"-p",
+							"x923",
+							"0xdd8941a4c217f2dbb0f38ba16551fe3e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 ECB no padding".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"-p",
+							"none",
+							"0xd7f480b25ee881f4b14d9893e6d76e7d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x61626364616263646162636461626364"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 GCM (NIST test case 2)".to_string(),
+						input: vec![
+							"-k",
+							"00000000000000000000000000000000",
+							"-n",
+							"000000000000000000000000",
+							"-m",
+							"gcm",
+							"0x0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x00000000000000000000000000000000"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 GCM with AAD (NIST test case 4)".to_string(),
+						input: vec![
+							"-k",
+							"feffe9928665731c6d6a8f9467308308",
+							"-n",
+							"cafebabefacedbaddecaf888",
+							"-a",
+							"0xfeedfacedeadbeeffeedfacedeadbeefabaddad2",
+							"-m",
+							"gcm",
+							"0x42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e0915bc94fbc3221a5db94fae95ae7121a47",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xd9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CCM (RFC 3610 packet vector 1)".to_string(),
+						input: vec![
+							"-k",
+							"c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+							"-n",
+							"00000003020100a0a1a2a3a4a5",
+							"-a",
+							"0x0001020304050607",
+							"-t",
+							"64",
+							"-m",
+							"ccm",
+							"0x588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x08090a0b0c0d0e0f101112131415161718191a1b1c1d1e"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "KeySize 128 CCM (NIST SP 800-38C example 1)".to_string(),
+						input: vec![
+							"-k",
+							"404142434445464748494a4b4c4d4e4f",
+							"-n",
+							"10111213141516",
+							"-a",
+							"0x0001020304050607",
+							"-t",
+							"32",
+							"-m",
+							"ccm",
+							"0x7162015b4dac255d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x20212223"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
 				],
 			),
 		]
@@ -924,4 +1418,60 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_tag_mismatch() {
+		let dec = |args: &[&str]| {
+			let app = commands().remove(1).app;
+			let matches =
+				app.get_matches_from(vec!["aes_dec"].into_iter().chain(args.iter().cloned()));
+			aes_dec(&matches)
+		};
+
+		// the last bit of the tag is flipped
+		let gcm = dec(&[
+			"-k",
+			"00000000000000000000000000000000",
+			"-n",
+			"000000000000000000000000",
+			"-m",
+			"gcm",
+			"0x0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bdde",
+		]);
+		assert_eq!(gcm, Err("Tag verification failed".to_string()));
+
+		let ccm = dec(&[
+			"-k",
+			"c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+			"-n",
+			"00000003020100a0a1a2a3a4a5",
+			"-a",
+			"0x0001020304050607",
+			"-t",
+			"64",
+			"-m",
+			"ccm",
+			"0x588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e1",
+		]);
+		assert_eq!(ccm, Err("Tag verification failed".to_string()));
+	}
+
+	#[test]
+	fn test_ctr_iv_size() {
+		let app = commands().remove(0).app;
+		let matches = app.get_matches_from(vec![
+			"aes_enc",
+			"-k",
+			"01010101010101010101010101010101",
+			"-i",
+			"0x01",
+			"-m",
+			"ctr",
+			"0x00",
+		]);
+		assert_eq!(
+			aes_enc(&matches),
+			Err("Invalid IV size (should be 128)".to_string())
+		);
+	}
 }
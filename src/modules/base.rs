@@ -1,30 +1,160 @@
-use clap::ArgMatches;
+use clap::{Arg, ArgMatches};
+use std::cell::RefCell;
 use std::io;
-use std::io::{BufRead, Read};
+use std::io::Read;
 use std::str::FromStr;
 
+pub fn in_format_arg<'a, 'b>() -> Arg<'a, 'b> {
+	Arg::with_name("IN_FORMAT")
+		.long("in-format")
+		.help(FORMAT_HELP_IN)
+		.takes_value(true)
+		.possible_values(&FORMAT_NAMES)
+		.required(false)
+}
+
+pub fn out_format_arg<'a, 'b>() -> Arg<'a, 'b> {
+	Arg::with_name("OUT_FORMAT")
+		.long("out-format")
+		.help(FORMAT_HELP_OUT)
+		.takes_value(true)
+		.possible_values(&FORMAT_NAMES)
+		.required(false)
+}
+
+const FORMAT_NAMES: [&str; 4] = ["raw", "hex", "base64", "utf8"];
+
+const FORMAT_HELP_IN: &str =
+	"Input format\nraw: Raw binary\nhex: Hex\nbase64: Base64\nutf8: UTF-8 string\n";
+
+const FORMAT_HELP_OUT: &str =
+	"Output format\nraw: Raw binary\nhex: Hex\nbase64: Base64\nutf8: UTF-8 string\n";
+
+pub enum Format {
+	Raw,
+	Hex,
+	Base64,
+	Utf8,
+}
+
+impl FromStr for Format {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"raw" => Ok(Format::Raw),
+			"hex" => Ok(Format::Hex),
+			"base64" => Ok(Format::Base64),
+			"utf8" => Ok(Format::Utf8),
+			_ => Err("Invalid format".to_string()),
+		}
+	}
+}
+
 pub fn input_string(matches: &ArgMatches) -> Result<String, String> {
+	if let Some(format) = matches.value_of("IN_FORMAT") {
+		let format = format.parse::<Format>()?;
+		let input = decode_input(input_bytes(matches)?, format)?;
+		return Ok(Hex::from(input).into());
+	}
+
 	match matches.value_of("INPUT") {
 		Some(input) => Ok(input.to_string()),
-		None => io::stdin()
-			.lock()
-			.lines()
-			.collect::<Result<Vec<String>, io::Error>>()
-			.map(|x| x.join("\n"))
-			.map_err(|_| "Invalid input".to_string()),
+		None => {
+			let input = String::from_utf8(read_stdin()?).map_err(|_| "Invalid input")?;
+			Ok(input.lines().collect::<Vec<&str>>().join("\n"))
+		}
 	}
 }
 
 pub fn input_bytes(matches: &ArgMatches) -> Result<Vec<u8>, String> {
 	match matches.value_of("INPUT") {
 		Some(input) => Ok(input.bytes().collect::<Vec<u8>>()),
-		None => io::stdin()
-			.bytes()
-			.collect::<Result<Vec<u8>, io::Error>>()
-			.map_err(|_| "Invalid input".to_string()),
+		None => read_stdin(),
+	}
+}
+
+thread_local! {
+	/// Stdin replacement, used by pipe to feed a stage with the output of the previous one
+	static STDIN: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Run f with the given bytes as stdin
+pub fn with_stdin<T, F: FnOnce() -> T>(input: Vec<u8>, f: F) -> T {
+	STDIN.with(|x| *x.borrow_mut() = Some(input));
+	let result = f();
+	STDIN.with(|x| *x.borrow_mut() = None);
+	result
+}
+
+/// Stdin, or the replacement set by with_stdin
+pub fn stdin() -> Box<dyn Read> {
+	match STDIN.with(|x| x.borrow().clone()) {
+		Some(input) => Box::new(io::Cursor::new(input)),
+		None => Box::new(io::stdin().lock()),
 	}
 }
 
+fn read_stdin() -> Result<Vec<u8>, String> {
+	let mut input = vec![];
+	stdin()
+		.read_to_end(&mut input)
+		.map_err(|_| "Invalid input".to_string())?;
+	Ok(input)
+}
+
+/// Decode the input of the given format to bytes
+fn decode_input(input: Vec<u8>, format: Format) -> Result<Vec<u8>, String> {
+	let text = || -> Result<String, String> {
+		let input = String::from_utf8(input.clone()).map_err(|_| "Invalid input")?;
+		Ok(input.trim_end_matches(&['\r', '\n'][..]).to_string())
+	};
+	let result = match format {
+		Format::Raw => input,
+		Format::Hex => text()?.trim().parse::<Hex>()?.into(),
+		Format::Base64 => {
+			let input = text()?
+				.chars()
+				.filter(|x| !x.is_whitespace())
+				.collect::<String>();
+			base64::decode(&input).map_err(|_| "Invalid base64")?
+		}
+		Format::Utf8 => text()?.into_bytes(),
+	};
+	Ok(result)
+}
+
+/// Encode the output lines (Hex) to the given format
+/// Raw output lines are concatenated without line breaks
+pub fn output_bytes(output: Vec<String>, format: Format) -> Result<Vec<u8>, String> {
+	let output = output
+		.iter()
+		.map(|x| x.parse::<Hex>().map(Vec::<u8>::from))
+		.collect::<Result<Vec<Vec<u8>>, String>>()
+		.map_err(|_| "Invalid output: not hex")?;
+
+	let result = match format {
+		Format::Raw => output.concat(),
+		Format::Hex => lines(output.into_iter().map(|x| Hex::from(x).into())),
+		Format::Base64 => lines(output.into_iter().map(|x| base64::encode(&x))),
+		Format::Utf8 => lines(
+			output
+				.into_iter()
+				.map(String::from_utf8)
+				.collect::<Result<Vec<String>, _>>()
+				.map_err(|_| "Invalid output: not UTF-8")?,
+		),
+	};
+	Ok(result)
+}
+
+fn lines<I: IntoIterator<Item = String>>(lines: I) -> Vec<u8> {
+	lines
+		.into_iter()
+		.map(|x| x + "\n")
+		.collect::<String>()
+		.into_bytes()
+}
+
 pub struct Hex(Vec<u8>);
 
 impl FromStr for Hex {
@@ -93,3 +223,47 @@ pub mod test {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode_input() {
+		assert_eq!(
+			decode_input(b"abc\n".to_vec(), Format::Raw),
+			Ok(b"abc\n".to_vec())
+		);
+		assert_eq!(
+			decode_input(b"0x616263\n".to_vec(), Format::Hex),
+			Ok(b"abc".to_vec())
+		);
+		assert_eq!(
+			decode_input(b"YWJj\n".to_vec(), Format::Base64),
+			Ok(b"abc".to_vec())
+		);
+		assert_eq!(
+			decode_input(b"abc\n".to_vec(), Format::Utf8),
+			Ok(b"abc".to_vec())
+		);
+	}
+
+	#[test]
+	fn test_output_bytes() {
+		let output = || vec!["0x616263".to_string(), "0x646566".to_string()];
+		assert_eq!(output_bytes(output(), Format::Raw), Ok(b"abcdef".to_vec()));
+		assert_eq!(
+			output_bytes(output(), Format::Hex),
+			Ok(b"0x616263\n0x646566\n".to_vec())
+		);
+		assert_eq!(
+			output_bytes(output(), Format::Base64),
+			Ok(b"YWJj\nZGVm\n".to_vec())
+		);
+		assert_eq!(
+			output_bytes(output(), Format::Utf8),
+			Ok(b"abc\ndef\n".to_vec())
+		);
+		assert!(output_bytes(vec!["abc".to_string()], Format::Raw).is_err());
+	}
+}
@@ -1,50 +1,264 @@
 use crate::modules::base::Hex;
-use crate::modules::{base, Command, Module};
+use crate::modules::{base, hash, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::iter::once;
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
-		desc: "Hex / base58 conversion".to_string(),
+		desc: "Hex / base58 / bech32 / SS58 conversion".to_string(),
 		commands: commands(),
 		get_cases: cases::cases,
 	}
 }
 
+struct Alphabet {
+	name: &'static str,
+	help: &'static str,
+	chars: &'static [u8; 58],
+	/// Monero: encoded in 8-byte blocks, check with Keccak-256
+	block: bool,
+}
+
+lazy_static! {
+	static ref RAW_ALPHABETS: Vec<Alphabet> = vec![
+		Alphabet {
+			name: "bitcoin",
+			help: "Bitcoin",
+			chars: bs58::alphabet::BITCOIN,
+			block: false,
+		},
+		Alphabet {
+			name: "ripple",
+			help: "Ripple",
+			chars: bs58::alphabet::RIPPLE,
+			block: false,
+		},
+		Alphabet {
+			name: "flickr",
+			help: "Flickr",
+			chars: bs58::alphabet::FLICKR,
+			block: false,
+		},
+		Alphabet {
+			name: "monero",
+			help: "Monero (Bitcoin alphabet in 8-byte blocks, check with Keccak-256)",
+			chars: bs58::alphabet::MONERO,
+			block: true,
+		},
+	];
+	static ref ALPHABETS: HashMap<&'static str, &'static Alphabet> =
+		RAW_ALPHABETS.iter().map(|x| (x.name, x)).collect();
+	static ref ALPHABET_NAMES: Vec<&'static str> = RAW_ALPHABETS.iter().map(|x| x.name).collect();
+	static ref ALPHABET_HELP: String = "Alphabet\n".to_string()
+		+ &RAW_ALPHABETS
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+}
+
+/// Encoded size of a Monero block by the byte length
+const MONERO_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
 pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 	vec![
 		Command {
 			app: SubCommand::with_name("h2b58")
 				.about("Convert hex to base58")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.default_value("bitcoin")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
 			f: h2b58,
 		},
 		Command {
 			app: SubCommand::with_name("h2b58c")
 				.about("Convert hex to base58 check")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.default_value("bitcoin")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
 			f: h2b58c,
 		},
 		Command {
 			app: SubCommand::with_name("b582h")
 				.about("Convert base58 to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.default_value("bitcoin")
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
 			f: b582h,
 		},
 		Command {
 			app: SubCommand::with_name("b58c2h")
 				.about("Convert base58 check to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.default_value("bitcoin")
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
 			f: b58c2h,
 		},
+		Command {
+			app: SubCommand::with_name("h2bech32")
+				.about("Convert hex to bech32")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("HRP")
+						.long("hrp")
+						.short("p")
+						.help("Human-readable part (bc, tb, cosmos, etc)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("VARIANT")
+						.long("variant")
+						.short("v")
+						.help("Variant\nbech32: Bech32 (BIP-173)\nbech32m: Bech32m (BIP-350)\nDefault: bech32m for witness version 1+, otherwise bech32\n")
+						.takes_value(true)
+						.possible_values(&["bech32", "bech32m"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("WITNESS_VERSION")
+						.long("witness-version")
+						.short("w")
+						.help("SegWit witness version (0-16), input is the witness program")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: h2bech32,
+		},
+		Command {
+			app: SubCommand::with_name("bech322h")
+				.about("Convert bech32 to hex (Human-readable part, Hex)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("VARIANT")
+						.long("variant")
+						.short("v")
+						.help("Variant\nbech32: Bech32 (BIP-173)\nbech32m: Bech32m (BIP-350)\nDefault: any\n")
+						.takes_value(true)
+						.possible_values(&["bech32", "bech32m"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("WITNESS")
+						.long("witness")
+						.short("w")
+						.help("SegWit address, output (Human-readable part, Witness version, Witness program)")
+						.required(false),
+				),
+			f: bech322h,
+		},
+		Command {
+			app: SubCommand::with_name("h2ss58")
+				.about("Convert public key to SS58 address (Substrate)")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Public key (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.short("p")
+						.help("Network prefix\n0: Polkadot\n2: Kusama\n42: Substrate\netc\n")
+						.takes_value(true)
+						.default_value("42")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: h2ss58,
+		},
+		Command {
+			app: SubCommand::with_name("ss582h")
+				.about("Convert SS58 address to public key (Network prefix, Public key)")
+				.arg(Arg::with_name("INPUT").required(false).index(1)),
+			f: ss582h,
+		},
 	]
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Bech32Variant {
+	Bech32,
+	Bech32m,
+}
+
+impl Bech32Variant {
+	fn constant(self) -> u32 {
+		match self {
+			Bech32Variant::Bech32 => 1,
+			Bech32Variant::Bech32m => 0x2bc8_30a3,
+		}
+	}
+
+	fn from_name(name: &str) -> Result<Self, String> {
+		match name {
+			"bech32" => Ok(Bech32Variant::Bech32),
+			"bech32m" => Ok(Bech32Variant::Bech32m),
+			_ => Err("Invalid variant".to_string()),
+		}
+	}
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Max length of a bech32 string (BIP-173)
+const BECH32_MAX_LEN: usize = 90;
+
 fn h2b58(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
 	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
 
-	let result = bs58::encode(input).into_string();
+	let alphabet = get_alphabet(matches)?;
+
+	let result = match alphabet.block {
+		true => monero_encode(&input, alphabet.chars),
+		false => bs58::encode(input)
+			.with_alphabet(alphabet.chars)
+			.into_string(),
+	};
 
 	Ok(vec![result])
 }
@@ -54,7 +268,19 @@ fn h2b58c(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
 
-	let result = bs58::encode(input).with_check().into_string();
+	let alphabet = get_alphabet(matches)?;
+
+	let result = match alphabet.block {
+		true => {
+			let checksum = Keccak256::digest(&input);
+			let input = [&input[..], &checksum[..4]].concat();
+			monero_encode(&input, alphabet.chars)
+		}
+		false => bs58::encode(input)
+			.with_alphabet(alphabet.chars)
+			.with_check()
+			.into_string(),
+	};
 
 	Ok(vec![result])
 }
@@ -62,9 +288,15 @@ fn h2b58c(matches: &ArgMatches) -> Result<Vec<String>, String> {
 fn b582h(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
-	let input = bs58::decode(&input)
-		.into_vec()
-		.map_err(|_| "Convert failed")?;
+	let alphabet = get_alphabet(matches)?;
+
+	let input = match alphabet.block {
+		true => monero_decode(&input, alphabet.chars)?,
+		false => bs58::decode(&input)
+			.with_alphabet(alphabet.chars)
+			.into_vec()
+			.map_err(|_| "Convert failed")?,
+	};
 	let result = Hex::from(input).into();
 
 	Ok(vec![result])
@@ -73,15 +305,434 @@ fn b582h(matches: &ArgMatches) -> Result<Vec<String>, String> {
 fn b58c2h(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
-	let input = bs58::decode(&input)
-		.with_check(None)
-		.into_vec()
-		.map_err(|_| "Convert failed")?;
+	let alphabet = get_alphabet(matches)?;
+
+	let input = match alphabet.block {
+		true => {
+			let input = monero_decode(&input, alphabet.chars)?;
+			if input.len() < 4 {
+				return Err("Convert failed".to_string());
+			}
+			let (input, checksum) = input.split_at(input.len() - 4);
+			if Keccak256::digest(input)[..4] != *checksum {
+				return Err("Convert failed: invalid checksum".to_string());
+			}
+			input.to_vec()
+		}
+		false => bs58::decode(&input)
+			.with_alphabet(alphabet.chars)
+			.with_check(None)
+			.into_vec()
+			.map_err(|_| "Convert failed")?,
+	};
 	let result = Hex::from(input).into();
 
 	Ok(vec![result])
 }
 
+fn get_alphabet(matches: &ArgMatches) -> Result<&'static Alphabet, String> {
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+	Ok(alphabet)
+}
+
+/// Monero base58: each 8-byte block is encoded into 11 chars, the last block is shorter
+fn monero_encode(input: &[u8], chars: &[u8; 58]) -> String {
+	input
+		.chunks(8)
+		.flat_map(|block| {
+			let mut value = block.iter().fold(0u64, |acc, x| (acc << 8) | *x as u64);
+			let mut digits = vec![chars[0]; MONERO_BLOCK_SIZES[block.len()]];
+			for digit in digits.iter_mut().rev() {
+				*digit = chars[(value % 58) as usize];
+				value /= 58;
+			}
+			digits
+		})
+		.map(char::from)
+		.collect()
+}
+
+fn monero_decode(input: &str, chars: &[u8; 58]) -> Result<Vec<u8>, String> {
+	let mut result = vec![];
+	for block in input.trim().as_bytes().chunks(11) {
+		let len = MONERO_BLOCK_SIZES
+			.iter()
+			.position(|x| *x == block.len())
+			.ok_or("Convert failed")?;
+		let mut value = 0u128;
+		for c in block {
+			let digit = chars.iter().position(|x| x == c).ok_or("Convert failed")?;
+			value = value * 58 + digit as u128;
+		}
+		if value >> (len * 8) != 0 {
+			return Err("Convert failed".to_string());
+		}
+		result.extend(&value.to_be_bytes()[16 - len..]);
+	}
+	Ok(result)
+}
+
+fn h2ss58(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input
+		.parse::<Hex>()
+		.map_err(|_| "Invalid public key")?
+		.into();
+
+	let prefix = matches.value_of("PREFIX").ok_or("Invalid prefix")?;
+	let prefix = prefix.parse::<u16>().map_err(|_| "Invalid prefix")?;
+
+	let result = ss58_encode(prefix, &input)?;
+
+	Ok(vec![result])
+}
+
+fn ss582h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let (prefix, public_key) = ss58_decode(input.trim())?;
+
+	let public_key: String = Hex::from(public_key).into();
+
+	let result = format!("({}, {})", prefix, public_key);
+
+	Ok(vec![result])
+}
+
+/// SS58 address (Substrate)
+pub fn ss58_encode(prefix: u16, public_key: &[u8]) -> Result<String, String> {
+	let mut data = match prefix {
+		0..=63 => vec![prefix as u8],
+		64..=16383 => vec![
+			((prefix & 0b0000_0000_1111_1100) >> 2) as u8 | 0b0100_0000,
+			(prefix >> 8) as u8 | ((prefix & 0b0000_0000_0000_0011) << 6) as u8,
+		],
+		_ => return Err("Invalid prefix".to_string()),
+	};
+	data.extend(public_key);
+
+	let checksum_len = match public_key.len() {
+		1 | 2 | 4 | 8 => 1,
+		32 | 33 => 2,
+		_ => return Err("Invalid public key".to_string()),
+	};
+	let checksum = ss58_checksum(&data);
+	data.extend(&checksum[..checksum_len]);
+
+	Ok(bs58::encode(data).into_string())
+}
+
+/// Decode an SS58 address into (network prefix, public key)
+pub fn ss58_decode(input: &str) -> Result<(u16, Vec<u8>), String> {
+	let input = bs58::decode(input)
+		.into_vec()
+		.map_err(|_| "Invalid address")?;
+
+	let (prefix, prefix_len) = match input.first() {
+		Some(0..=63) => (input[0] as u16, 1),
+		Some(64..=127) if input.len() > 1 => {
+			let lower = (input[0] << 2) | (input[1] >> 6);
+			let upper = input[1] & 0b0011_1111;
+			(lower as u16 | (upper as u16) << 8, 2)
+		}
+		_ => return Err("Invalid address: prefix".to_string()),
+	};
+
+	let checksum_len = match input.len() - prefix_len {
+		2 | 3 | 5 | 9 => 1,
+		34 | 35 => 2,
+		_ => return Err("Invalid address: length".to_string()),
+	};
+	let (data, checksum) = input.split_at(input.len() - checksum_len);
+	if ss58_checksum(data)[..checksum_len] != *checksum {
+		return Err("Invalid address: checksum".to_string());
+	}
+
+	Ok((prefix, data[prefix_len..].to_vec()))
+}
+
+fn ss58_checksum(data: &[u8]) -> Vec<u8> {
+	hash::blake2b_hash(64, &[b"SS58PRE", data].concat())
+}
+
+fn h2bech32(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let hrp = matches.value_of("HRP").ok_or("Invalid hrp")?;
+
+	let witness_version = match matches.value_of("WITNESS_VERSION") {
+		Some(v) => Some(v.parse::<u8>().map_err(|_| "Invalid witness version")?),
+		None => None,
+	};
+
+	let variant = match (matches.value_of("VARIANT"), witness_version) {
+		(Some(variant), _) => Bech32Variant::from_name(variant)?,
+		(None, Some(v)) if v > 0 => Bech32Variant::Bech32m,
+		(None, _) => Bech32Variant::Bech32,
+	};
+
+	let result = match witness_version {
+		Some(v) => segwit_encode(hrp, v, &input, variant)?,
+		None => bech32_encode(hrp, &convert_bits(&input, 8, 5, true)?, variant)?,
+	};
+
+	Ok(vec![result])
+}
+
+fn bech322h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input = input.trim();
+
+	let variant = match matches.value_of("VARIANT") {
+		Some(variant) => Some(Bech32Variant::from_name(variant)?),
+		None => None,
+	};
+
+	let (hrp, data, actual_variant) = bech32_decode(input, variant)?;
+
+	let result = match matches.is_present("WITNESS") {
+		true => {
+			let (version, program) = segwit_program(&data, actual_variant)?;
+			let program: String = Hex::from(program).into();
+			format!("({}, {}, {})", hrp, version, program)
+		}
+		false => {
+			let data: String = Hex::from(convert_bits(&data, 5, 8, false)?).into();
+			format!("({}, {})", hrp, data)
+		}
+	};
+
+	Ok(vec![result])
+}
+
+/// Encode a SegWit address (BIP-173, BIP-350)
+pub fn segwit_encode(
+	hrp: &str,
+	version: u8,
+	program: &[u8],
+	variant: Bech32Variant,
+) -> Result<String, String> {
+	if version > 16 {
+		return Err("Invalid witness version".to_string());
+	}
+	let data = once(version)
+		.chain(convert_bits(program, 8, 5, true)?)
+		.collect::<Vec<_>>();
+	segwit_program(&data, variant)?;
+	bech32_encode(hrp, &data, variant)
+}
+
+/// Decode a SegWit address into (human-readable part, witness version, witness program)
+pub fn segwit_decode(input: &str) -> Result<(String, u8, Vec<u8>), String> {
+	let (hrp, data, variant) = bech32_decode(input, None)?;
+	let (version, program) = segwit_program(&data, variant)?;
+	Ok((hrp, version, program))
+}
+
+/// Check and split SegWit data (5-bit) into (witness version, witness program)
+fn segwit_program(data: &[u8], variant: Bech32Variant) -> Result<(u8, Vec<u8>), String> {
+	let (version, program) = data.split_first().ok_or("Invalid witness version")?;
+	let version = *version;
+	if version > 16 {
+		return Err("Invalid witness version".to_string());
+	}
+	let expected_variant = match version {
+		0 => Bech32Variant::Bech32,
+		_ => Bech32Variant::Bech32m,
+	};
+	if variant != expected_variant {
+		return Err(format!(
+			"Invalid variant: witness version {} should use {}",
+			version,
+			match expected_variant {
+				Bech32Variant::Bech32 => "bech32",
+				Bech32Variant::Bech32m => "bech32m",
+			}
+		));
+	}
+	let program = convert_bits(program, 5, 8, false)?;
+	match (version, program.len()) {
+		(0, 20) | (0, 32) => (),
+		(0, _) => return Err("Invalid witness program length".to_string()),
+		(_, 2..=40) => (),
+		_ => return Err("Invalid witness program length".to_string()),
+	}
+	Ok((version, program))
+}
+
+/// Encode the data (5-bit groups) with the human-readable part
+pub fn bech32_encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> Result<String, String> {
+	if hrp.is_empty() || hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+		return Err("Invalid hrp".to_string());
+	}
+	if hrp.len() + 1 + data.len() + 6 > BECH32_MAX_LEN {
+		return Err(format!(
+			"Invalid bech32: longer than {} characters",
+			BECH32_MAX_LEN
+		));
+	}
+	let hrp = hrp.to_lowercase();
+
+	let values = bech32_hrp_expand(&hrp)
+		.into_iter()
+		.chain(data.iter().cloned())
+		.chain([0u8; 6].iter().cloned())
+		.collect::<Vec<_>>();
+	let polymod = bech32_polymod(&values) ^ variant.constant();
+	let checksum = (0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8);
+
+	let result = data
+		.iter()
+		.cloned()
+		.chain(checksum)
+		.map(|x| BECH32_CHARSET[x as usize] as char);
+
+	Ok(hrp.chars().chain(once('1')).chain(result).collect())
+}
+
+/// Decode into (human-readable part, data in 5-bit groups, variant)
+fn bech32_decode(
+	input: &str,
+	variant: Option<Bech32Variant>,
+) -> Result<(String, Vec<u8>, Bech32Variant), String> {
+	// also bounds the error location search below
+	if input.len() > BECH32_MAX_LEN {
+		return Err(format!(
+			"Invalid bech32: longer than {} characters",
+			BECH32_MAX_LEN
+		));
+	}
+	if input.chars().any(|c| c.is_lowercase()) && input.chars().any(|c| c.is_uppercase()) {
+		return Err("Invalid bech32: mixed case".to_string());
+	}
+	let input = input.to_lowercase();
+
+	let pos = input.rfind('1').ok_or("Invalid bech32: no separator")?;
+	if pos == 0 || pos + 7 > input.len() {
+		return Err("Invalid bech32: invalid separator position".to_string());
+	}
+
+	let (hrp, data) = (&input[..pos], &input[pos + 1..]);
+	if let Some(i) = hrp.bytes().position(|c| !(33..=126).contains(&c)) {
+		return Err(point_at(&input, i, "Invalid bech32: invalid character at"));
+	}
+	let data = data
+		.bytes()
+		.enumerate()
+		.map(|(i, c)| {
+			BECH32_CHARSET
+				.iter()
+				.position(|x| *x == c)
+				.map(|x| x as u8)
+				.ok_or_else(|| {
+					point_at(&input, pos + 1 + i, "Invalid bech32: invalid character at")
+				})
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let variants = match variant {
+		Some(variant) => vec![variant],
+		None => vec![Bech32Variant::Bech32, Bech32Variant::Bech32m],
+	};
+
+	let hrp_expanded = bech32_hrp_expand(hrp);
+	let verify = |data: &[u8]| {
+		let polymod = bech32_polymod(&[&hrp_expanded[..], data].concat());
+		variants.iter().find(|x| x.constant() == polymod).cloned()
+	};
+
+	match verify(&data) {
+		Some(variant) => Ok((hrp.to_string(), data[..data.len() - 6].to_vec(), variant)),
+		None => {
+			// a substitution error can be located as the checksum detects up to 4 errors
+			let position = (0..data.len()).find(|i| {
+				(0..32u8).filter(|x| *x != data[*i]).any(|x| {
+					let mut data = data.clone();
+					data[*i] = x;
+					verify(&data).is_some()
+				})
+			});
+			match position {
+				Some(i) => Err(point_at(
+					&input,
+					pos + 1 + i,
+					"Invalid bech32: invalid checksum, likely error at",
+				)),
+				None => Err("Invalid bech32: invalid checksum".to_string()),
+			}
+		}
+	}
+}
+
+/// Error message with the input and a caret under the position
+fn point_at(input: &str, position: usize, message: &str) -> String {
+	format!(
+		"{} position {}\n{}\n{}^",
+		message,
+		position,
+		input,
+		" ".repeat(position)
+	)
+}
+
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+	hrp.bytes()
+		.map(|x| x >> 5)
+		.chain(once(0))
+		.chain(hrp.bytes().map(|x| x & 31))
+		.collect()
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+	const GENERATOR: [u32; 5] = [
+		0x3b6a_57b2,
+		0x2650_8e6d,
+		0x1ea1_19fa,
+		0x3d42_33dd,
+		0x2a14_62b3,
+	];
+	values.iter().fold(1u32, |chk, value| {
+		let top = chk >> 25;
+		let chk = ((chk & 0x1ff_ffff) << 5) ^ *value as u32;
+		GENERATOR
+			.iter()
+			.enumerate()
+			.filter(|(i, _)| (top >> i) & 1 == 1)
+			.fold(chk, |chk, (_, g)| chk ^ g)
+	})
+}
+
+/// Regroup bits (8-bit to 5-bit with padding, or 5-bit to 8-bit)
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+	let mut result = vec![];
+	let (mut acc, mut bits) = (0u32, 0u32);
+	let max = (1u32 << to) - 1;
+	for value in data {
+		let value = *value as u32;
+		if value >> from != 0 {
+			return Err("Convert failed".to_string());
+		}
+		acc = (acc << from) | value;
+		bits += from;
+		while bits >= to {
+			bits -= to;
+			result.push(((acc >> bits) & max) as u8);
+		}
+	}
+	if pad {
+		if bits > 0 {
+			result.push(((acc << (to - bits)) & max) as u8);
+		}
+	} else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+		return Err("Convert failed: invalid padding".to_string());
+	}
+	Ok(result)
+}
+
 mod cases {
 	use crate::modules::Case;
 	use linked_hash_map::LinkedHashMap;
@@ -119,6 +770,46 @@ mod cases {
 						is_test: true,
 						since: "0.1.0".to_string(),
 					},
+					Case {
+						desc: "Ripple".to_string(),
+						input: vec![
+							"-a",
+							"ripple",
+							"0x00b5f762798a53d543a014caf8b297cff8f2f937e8bf32ba9f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Monero".to_string(),
+						input: vec!["-a", "monero", "0xffffffffffffffff"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["jpXCZedGfVQ"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Monero".to_string(),
+						input: vec!["-a", "monero", "0x0000"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["111"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
 				],
 			),
 			(
@@ -152,41 +843,366 @@ mod cases {
 						is_test: true,
 						since: "0.1.0".to_string(),
 					},
+					Case {
+						desc: "Monero".to_string(),
+						input: vec!["-a", "monero", "0x1234"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["1A4nc3C7T"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
 				],
 			),
 			(
 				"b582h",
-				vec![Case {
-					desc: "".to_string(),
-					input: vec!["12dvBhvPEPniQmBmgvj4qpJEodT7P"]
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["12dvBhvPEPniQmBmgvj4qpJEodT7P"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x0075774f5d9963c021009a58d7d2d8e83771dd6c7a"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.1.0".to_string(),
+					},
+					Case {
+						desc: "Monero".to_string(),
+						input: vec!["-a", "monero", "jpXCZedGfVQ"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0xffffffffffffffff"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"b58c2h",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["1Bi6zFVNtntP5MtDraNrAD7e469ifsQMwF"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x0075774f5d9963c021009a58d7d2d8e83771dd6c7a"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.1.0".to_string(),
+					},
+					Case {
+						desc: "Ripple".to_string(),
+						input: vec!["-a", "ripple", "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x00b5f762798a53d543a014caf8b297cff8f2f937e8"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Monero".to_string(),
+						input: vec!["-a", "monero", "1A4nc3C7T"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x1234"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"h2bech32",
+				vec![
+					Case {
+						desc: "Cosmos".to_string(),
+						input: vec!["-p", "cosmos", "0x751e76e8199196d454941c45d1b3a323f1433bd6"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SegWit v0 (BIP-173)".to_string(),
+						input: vec![
+							"-p",
+							"bc",
+							"-w",
+							"0",
+							"0x751e76e8199196d454941c45d1b3a323f1433bd6",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Taproot, SegWit v1 (BIP-350)".to_string(),
+						input: vec![
+							"-p",
+							"bc",
+							"-w",
+							"1",
+							"0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+						]
 						.into_iter()
 						.map(Into::into)
 						.collect(),
-					output: vec!["0x0075774f5d9963c021009a58d7d2d8e83771dd6c7a"]
+						output: vec![
+							"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+						]
 						.into_iter()
 						.map(Into::into)
 						.collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.1.0".to_string(),
-				}],
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bech32m".to_string(),
+						input: vec![
+							"-p",
+							"abcdef",
+							"-v",
+							"bech32m",
+							"0xffbbcdeb38bdab49ca307b9ac5a928398a418820",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 			(
-				"b58c2h",
-				vec![Case {
-					desc: "".to_string(),
-					input: vec!["1Bi6zFVNtntP5MtDraNrAD7e469ifsQMwF"]
+				"bech322h",
+				vec![
+					Case {
+						desc: "Cosmos".to_string(),
+						input: vec!["cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["(cosmos, 0x751e76e8199196d454941c45d1b3a323f1433bd6)"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SegWit v0 (BIP-173)".to_string(),
+						input: vec!["-w", "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["(bc, 0, 0x751e76e8199196d454941c45d1b3a323f1433bd6)"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Taproot, SegWit v1 (BIP-350)".to_string(),
+						input: vec![
+							"-w",
+							"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(bc, 1, 0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798)",
+						]
 						.into_iter()
 						.map(Into::into)
 						.collect(),
-					output: vec!["0x0075774f5d9963c021009a58d7d2d8e83771dd6c7a"]
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Bech32m (BIP-350)".to_string(),
+						input: vec![
+							"-v",
+							"bech32m",
+							"abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+						]
 						.into_iter()
 						.map(Into::into)
 						.collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.1.0".to_string(),
-				}],
+						output: vec!["(abcdef, 0xffbbcdeb38bdab49ca307b9ac5a928398a418820)"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Empty data (BIP-173)".to_string(),
+						input: vec!["A12UEL5L"].into_iter().map(Into::into).collect(),
+						output: vec!["(a, 0x)"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"h2ss58",
+				vec![
+					Case {
+						desc: "Substrate".to_string(),
+						input: vec![
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Polkadot".to_string(),
+						input: vec![
+							"-p",
+							"0",
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Kusama".to_string(),
+						input: vec![
+							"-p",
+							"2",
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Two-byte prefix".to_string(),
+						input: vec![
+							"-p",
+							"255",
+							"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["yGHXkYLYqxijLKKfd9Q2CB9shRVu8rPNBS53wvwGTutYg4zTg"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"ss582h",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec![
+							"(42, 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Two-byte prefix".to_string(),
+						input: vec!["yGHXkYLYqxijLKKfd9Q2CB9shRVu8rPNBS53wvwGTutYg4zTg"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec![
+							"(255, 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 		]
 		.into_iter()
@@ -203,4 +1219,22 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_bech32_max_len() {
+		let input = format!("a1{}", "q".repeat(3000));
+		assert_eq!(
+			bech32_decode(&input, None).err(),
+			Some("Invalid bech32: longer than 90 characters".to_string())
+		);
+
+		assert_eq!(
+			bech32_encode("a", &[0; 82], Bech32Variant::Bech32).map(|x| x.len()),
+			Ok(90)
+		);
+		assert_eq!(
+			bech32_encode("a", &[0; 83], Bech32Variant::Bech32),
+			Err("Invalid bech32: longer than 90 characters".to_string())
+		);
+	}
 }
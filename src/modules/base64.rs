@@ -1,6 +1,8 @@
 use crate::modules::base::Hex;
 use crate::modules::{base, Command, Module};
-use clap::{Arg, ArgMatches, SubCommand};
+use clap::{AppSettings, Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
@@ -10,18 +12,110 @@ pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	}
 }
 
+struct Alphabet {
+	name: &'static str,
+	help: &'static str,
+	chars: &'static [u8; 64],
+	/// Whether padded by default
+	pad: bool,
+}
+
+const STANDARD_CHARS: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Line length of MIME (RFC 2045)
+const MIME_LINE_LEN: usize = 76;
+
+lazy_static! {
+	static ref RAW_ALPHABETS: Vec<Alphabet> = vec![
+		Alphabet {
+			name: "standard",
+			help: "Standard (RFC 4648, +/)",
+			chars: STANDARD_CHARS,
+			pad: true,
+		},
+		Alphabet {
+			name: "urlsafe",
+			help: "URL and filename safe (RFC 4648, -_)",
+			chars: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+			pad: true,
+		},
+		Alphabet {
+			name: "crypt",
+			help: "crypt (./0-9A-Za-z, no padding)",
+			chars: b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+			pad: false,
+		},
+		Alphabet {
+			name: "bcrypt",
+			help: "bcrypt (./A-Za-z0-9, no padding)",
+			chars: b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+			pad: false,
+		},
+	];
+	static ref ALPHABETS: HashMap<&'static str, &'static Alphabet> =
+		RAW_ALPHABETS.iter().map(|x| (x.name, x)).collect();
+	static ref ALPHABET_NAMES: Vec<&'static str> = RAW_ALPHABETS.iter().map(|x| x.name).collect();
+	static ref ALPHABET_HELP: String = "Alphabet\n".to_string()
+		+ &RAW_ALPHABETS
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+	static ref DECODE_ALPHABET_HELP: String =
+		ALPHABET_HELP.to_string() + "Default: auto-detect standard / urlsafe\n";
+}
+
 pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 	vec![
 		Command {
 			app: SubCommand::with_name("h2b64")
 				.about("Convert hex to base64")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.default_value("standard")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NO_PAD")
+						.long("no-pad")
+						.short("n")
+						.help("No padding")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MIME")
+						.long("mime")
+						.short("m")
+						.help("MIME (RFC 2045): wrap lines at 76 columns")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
 			f: h2b64,
 		},
 		Command {
 			app: SubCommand::with_name("b642h")
-				.about("Convert base64 to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.about("Convert base64 to hex (whitespace ignored, padding optional)")
+				// URL safe base64 may start with a hyphen
+				.setting(AppSettings::AllowLeadingHyphen)
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&DECODE_ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&ALPHABET_NAMES)
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
 			f: b642h,
 		},
 	]
@@ -32,20 +126,77 @@ fn h2b64(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
 
-	let result = base64::encode(&input);
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
 
-	Ok(vec![result])
+	let pad = alphabet.pad && !matches.is_present("NO_PAD");
+
+	let mut result = base64::encode_config(&input, base64::STANDARD_NO_PAD)
+		.bytes()
+		.map(|c| translate(c, STANDARD_CHARS, alphabet.chars))
+		.collect::<Option<String>>()
+		.ok_or("Convert failed")?;
+	if pad {
+		while result.len() % 4 != 0 {
+			result.push('=');
+		}
+	}
+
+	let result = match matches.is_present("MIME") {
+		true => result
+			.as_bytes()
+			.chunks(MIME_LINE_LEN)
+			.map(|x| String::from_utf8_lossy(x).to_string())
+			.collect(),
+		false => vec![result],
+	};
+
+	Ok(result)
 }
 
 fn b642h(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
-	let result = base64::decode(&input).map_err(|_| "Convert failed")?;
+	let input = input
+		.chars()
+		.filter(|c| !c.is_whitespace())
+		.collect::<String>();
+	let input = input.trim_end_matches('=');
+
+	let alphabet = match matches.value_of("ALPHABET") {
+		Some(alphabet) => alphabet,
+		None => detect_alphabet(input)?,
+	};
+	let alphabet = ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+
+	let input = input
+		.bytes()
+		.map(|c| translate(c, alphabet.chars, STANDARD_CHARS))
+		.collect::<Option<String>>()
+		.ok_or("Convert failed")?;
+
+	let result =
+		base64::decode_config(&input, base64::STANDARD_NO_PAD).map_err(|_| "Convert failed")?;
 	let result = Hex::from(result).into();
 
 	Ok(vec![result])
 }
 
+/// Map a char of one alphabet to the char at the same position of another one
+fn translate(c: u8, from: &[u8; 64], to: &[u8; 64]) -> Option<char> {
+	from.iter().position(|x| *x == c).map(|i| to[i] as char)
+}
+
+fn detect_alphabet(input: &str) -> Result<&'static str, String> {
+	let standard = input.contains(&['+', '/'][..]);
+	let urlsafe = input.contains(&['-', '_'][..]);
+	match (standard, urlsafe) {
+		(true, true) => Err("Convert failed: mixed alphabets".to_string()),
+		(false, true) => Ok("urlsafe"),
+		_ => Ok("standard"),
+	}
+}
+
 mod cases {
 	use crate::modules::Case;
 	use linked_hash_map::LinkedHashMap;
@@ -54,25 +205,98 @@ mod cases {
 		vec![
 			(
 				"h2b64",
-				vec![Case {
-					desc: "".to_string(),
-					input: vec!["0x616263"].into_iter().map(Into::into).collect(),
-					output: vec!["YWJj"].into_iter().map(Into::into).collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.1.0".to_string(),
-				}],
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						output: vec!["YWJj"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.1.0".to_string(),
+					},
+					Case {
+						desc: "URL safe, no padding".to_string(),
+						input: vec!["-a", "urlsafe", "-n", "0xfbff"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["-_8"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "bcrypt".to_string(),
+						input: vec!["-a", "bcrypt", "0x616263ff"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["WUHh9u"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "MIME".to_string(),
+						input: vec![
+							"-m",
+							"0x000102030405060708090001020304050607080900010203040506070809000102030405060708090001020304050607080900010203040506070809",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"AAECAwQFBgcICQABAgMEBQYHCAkAAQIDBAUGBwgJAAECAwQFBgcICQABAgMEBQYHCAkAAQIDBAUG",
+							"BwgJ",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 			(
 				"b642h",
-				vec![Case {
-					desc: "".to_string(),
-					input: vec!["YWJj"].into_iter().map(Into::into).collect(),
-					output: vec!["0x616263"].into_iter().map(Into::into).collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.1.0".to_string(),
-				}],
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["YWJj"].into_iter().map(Into::into).collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.1.0".to_string(),
+					},
+					Case {
+						desc: "URL safe, without padding (auto-detected)".to_string(),
+						input: vec!["-_8"].into_iter().map(Into::into).collect(),
+						output: vec!["0xfbff"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Whitespace ignored".to_string(),
+						input: vec!["YWJj\nZA=="].into_iter().map(Into::into).collect(),
+						output: vec!["0x61626364"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "crypt".to_string(),
+						input: vec!["-a", "crypt", "MK7Xzk"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x616263ff"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 		]
 		.into_iter()
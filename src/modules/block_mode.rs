@@ -0,0 +1,578 @@
+//! Block cipher modes shared by the 128-bit block ciphers (AES, SM4)
+//! The cipher is given as a block encrypt function
+
+use std::str::FromStr;
+
+pub const BLOCK_SIZE: usize = 16;
+
+pub type Block = [u8; BLOCK_SIZE];
+
+pub const PADDING_NAMES: [&str; 5] = ["pkcs7", "zero", "iso7816", "x923", "none"];
+
+pub const PADDING_HELP: &str = "Padding for ecb, cbc\npkcs7: PKCS#7\nzero: Zero padding\niso7816: ISO/IEC 7816-4\nx923: ANSI X9.23\nnone: No padding\n";
+
+#[derive(Clone, Copy)]
+pub enum Padding {
+	Pkcs7,
+	Zero,
+	Iso7816,
+	X923,
+	None,
+}
+
+impl FromStr for Padding {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"pkcs7" => Ok(Padding::Pkcs7),
+			"zero" => Ok(Padding::Zero),
+			"iso7816" => Ok(Padding::Iso7816),
+			"x923" => Ok(Padding::X923),
+			"none" => Ok(Padding::None),
+			_ => Err("Invalid padding".to_string()),
+		}
+	}
+}
+
+impl Padding {
+	/// Pad to a multiple of the block size
+	pub fn pad(self, input: &[u8]) -> Result<Vec<u8>, String> {
+		let mut result = input.to_vec();
+		let pad_len = BLOCK_SIZE - input.len() % BLOCK_SIZE;
+		match self {
+			Padding::Pkcs7 => result.resize(input.len() + pad_len, pad_len as u8),
+			Padding::Zero => result = pad_block(input),
+			Padding::Iso7816 => {
+				result.push(0x80);
+				result.resize(input.len() + pad_len, 0);
+			}
+			Padding::X923 => {
+				result.resize(input.len() + pad_len - 1, 0);
+				result.push(pad_len as u8);
+			}
+			Padding::None => {
+				if pad_len != BLOCK_SIZE {
+					return Err(
+						"Invalid input length (should be a multiple of 16 bytes without padding)"
+							.to_string(),
+					);
+				}
+			}
+		}
+		Ok(result)
+	}
+
+	/// Remove the padding, the input length should be a multiple of the block size
+	pub fn unpad(self, input: &[u8]) -> Result<Vec<u8>, String> {
+		let invalid = || "Invalid padding".to_string();
+		let len = match self {
+			Padding::Pkcs7 | Padding::X923 => {
+				let pad_len = *input.last().ok_or_else(invalid)? as usize;
+				if pad_len == 0 || pad_len > BLOCK_SIZE || pad_len > input.len() {
+					return Err(invalid());
+				}
+				let len = input.len() - pad_len;
+				let expected = match self {
+					Padding::Pkcs7 => pad_len as u8,
+					_ => 0,
+				};
+				if input[len..input.len() - 1].iter().any(|x| *x != expected) {
+					return Err(invalid());
+				}
+				len
+			}
+			Padding::Zero => input.len() - input.iter().rev().take_while(|x| **x == 0).count(),
+			Padding::Iso7816 => {
+				let zeros = input.iter().rev().take_while(|x| **x == 0).count();
+				if zeros >= BLOCK_SIZE
+					|| zeros == input.len()
+					|| input[input.len() - zeros - 1] != 0x80
+				{
+					return Err(invalid());
+				}
+				input.len() - zeros - 1
+			}
+			Padding::None => input.len(),
+		};
+		Ok(input[..len].to_vec())
+	}
+}
+
+/// ECB encrypt, the input is padded with the given padding
+pub fn ecb_encrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	input: &[u8],
+	padding: Padding,
+) -> Result<Vec<u8>, String> {
+	let input = padding.pad(input)?;
+	Ok(input
+		.chunks(BLOCK_SIZE)
+		.flat_map(|chunk| encrypt_block(&to_block(chunk)).to_vec())
+		.collect())
+}
+
+/// ECB decrypt, the padding is removed from the result
+pub fn ecb_decrypt<F: Fn(&Block) -> Block>(
+	decrypt_block: F,
+	input: &[u8],
+	padding: Padding,
+) -> Result<Vec<u8>, String> {
+	check_block_aligned(input)?;
+	let result = input
+		.chunks(BLOCK_SIZE)
+		.flat_map(|chunk| decrypt_block(&to_block(chunk)).to_vec())
+		.collect::<Vec<u8>>();
+	padding.unpad(&result)
+}
+
+/// CBC encrypt, the input is padded with the given padding
+pub fn cbc_encrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+	padding: Padding,
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	let input = padding.pad(input)?;
+	let mut result = Vec::with_capacity(input.len());
+	input.chunks(BLOCK_SIZE).fold(iv, |prev, chunk| {
+		let block = encrypt_block(&xor_block(&prev, chunk));
+		result.extend(&block);
+		block
+	});
+	Ok(result)
+}
+
+/// CBC decrypt, the padding is removed from the result
+pub fn cbc_decrypt<F: Fn(&Block) -> Block>(
+	decrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+	padding: Padding,
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	check_block_aligned(input)?;
+	let mut result = Vec::with_capacity(input.len());
+	input.chunks(BLOCK_SIZE).fold(iv, |prev, chunk| {
+		let block = to_block(chunk);
+		result.extend(&xor_block(&prev, &decrypt_block(&block)));
+		block
+	});
+	padding.unpad(&result)
+}
+
+/// CFB (128-bit segment) encrypt
+pub fn cfb_encrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	let mut result = Vec::with_capacity(input.len());
+	input.chunks(BLOCK_SIZE).fold(iv, |prev, chunk| {
+		let cipher = xor_block(&encrypt_block(&prev), chunk);
+		result.extend(&cipher[..chunk.len()]);
+		cipher
+	});
+	Ok(result)
+}
+
+/// CFB (128-bit segment) decrypt
+pub fn cfb_decrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	let mut result = Vec::with_capacity(input.len());
+	input.chunks(BLOCK_SIZE).fold(iv, |prev, chunk| {
+		let plain = xor_block(&encrypt_block(&prev), chunk);
+		result.extend(&plain[..chunk.len()]);
+		to_block(chunk)
+	});
+	Ok(result)
+}
+
+/// OFB encrypt (or decrypt)
+pub fn ofb_process<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	let mut result = Vec::with_capacity(input.len());
+	input.chunks(BLOCK_SIZE).fold(iv, |prev, chunk| {
+		let key_stream = encrypt_block(&prev);
+		result.extend(&xor_block(&key_stream, chunk)[..chunk.len()]);
+		key_stream
+	});
+	Ok(result)
+}
+
+/// CTR encrypt (or decrypt), the IV is the initial counter block
+pub fn ctr_process<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	iv: &[u8],
+	input: &[u8],
+) -> Result<Vec<u8>, String> {
+	let iv = check_iv(iv)?;
+	Ok(counter_process(&encrypt_block, &iv, input, inc_block))
+}
+
+/// GCM encrypt (NIST SP 800-38D)
+/// Output: cipher || tag
+pub fn gcm_encrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	nonce: &[u8],
+	aad: &[u8],
+	input: &[u8],
+	tag_len: usize,
+) -> Result<Vec<u8>, String> {
+	check_gcm_params(nonce, tag_len)?;
+
+	let h = encrypt_block(&[0u8; BLOCK_SIZE]);
+	let j0 = gcm_j0(&h, nonce);
+
+	let mut result = counter_process(&encrypt_block, &inc32(&j0), input, inc32);
+	let tag = gcm_tag(&encrypt_block, &h, &j0, aad, &result, tag_len);
+	result.extend(tag);
+
+	Ok(result)
+}
+
+/// GCM decrypt (NIST SP 800-38D)
+/// Input: cipher || tag
+pub fn gcm_decrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	nonce: &[u8],
+	aad: &[u8],
+	input: &[u8],
+	tag_len: usize,
+) -> Result<Vec<u8>, String> {
+	check_gcm_params(nonce, tag_len)?;
+
+	let (cipher, tag) = split_tag(input, tag_len)?;
+
+	let h = encrypt_block(&[0u8; BLOCK_SIZE]);
+	let j0 = gcm_j0(&h, nonce);
+
+	let expected_tag = gcm_tag(&encrypt_block, &h, &j0, aad, cipher, tag_len);
+	check_tag(&expected_tag, tag)?;
+
+	Ok(counter_process(&encrypt_block, &inc32(&j0), cipher, inc32))
+}
+
+/// CCM encrypt (NIST SP 800-38C)
+/// Output: cipher || tag
+pub fn ccm_encrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	nonce: &[u8],
+	aad: &[u8],
+	input: &[u8],
+	tag_len: usize,
+) -> Result<Vec<u8>, String> {
+	check_ccm_params(nonce, input, tag_len)?;
+
+	let mac = ccm_mac(&encrypt_block, nonce, aad, input, tag_len);
+
+	let ctr0 = ccm_ctr0(nonce);
+	let mut result = counter_process(&encrypt_block, &inc_block(&ctr0), input, inc_block);
+	let tag = counter_process(&encrypt_block, &ctr0, &mac, inc_block);
+	result.extend(tag);
+
+	Ok(result)
+}
+
+/// CCM decrypt (NIST SP 800-38C)
+/// Input: cipher || tag
+pub fn ccm_decrypt<F: Fn(&Block) -> Block>(
+	encrypt_block: F,
+	nonce: &[u8],
+	aad: &[u8],
+	input: &[u8],
+	tag_len: usize,
+) -> Result<Vec<u8>, String> {
+	let (cipher, tag) = split_tag(input, tag_len)?;
+
+	check_ccm_params(nonce, cipher, tag_len)?;
+
+	let ctr0 = ccm_ctr0(nonce);
+	let result = counter_process(&encrypt_block, &inc_block(&ctr0), cipher, inc_block);
+	let tag = counter_process(&encrypt_block, &ctr0, tag, inc_block);
+
+	let expected_tag = ccm_mac(&encrypt_block, nonce, aad, &result, tag_len);
+	check_tag(&expected_tag, &tag)?;
+
+	Ok(result)
+}
+
+fn check_iv(iv: &[u8]) -> Result<Block, String> {
+	if iv.len() != BLOCK_SIZE {
+		return Err("Invalid IV size (should be 128)".to_string());
+	}
+	Ok(to_block(iv))
+}
+
+fn check_block_aligned(input: &[u8]) -> Result<(), String> {
+	if input.len() % BLOCK_SIZE != 0 {
+		return Err("Invalid input length (should be a multiple of 16 bytes)".to_string());
+	}
+	Ok(())
+}
+
+/// Copy a chunk (at most one block) into a block, padded with zeros
+fn to_block(chunk: &[u8]) -> Block {
+	let mut block = [0u8; BLOCK_SIZE];
+	block[..chunk.len()].copy_from_slice(chunk);
+	block
+}
+
+/// Xor a block with a chunk (at most one block), the rest of the block is kept
+fn xor_block(block: &Block, chunk: &[u8]) -> Block {
+	let mut result = *block;
+	for (a, b) in result.iter_mut().zip(chunk) {
+		*a ^= b;
+	}
+	result
+}
+
+fn check_gcm_params(nonce: &[u8], tag_len: usize) -> Result<(), String> {
+	if nonce.is_empty() {
+		return Err("Invalid nonce (should not be empty)".to_string());
+	}
+	match tag_len {
+		4 | 8 | 12..=16 => Ok(()),
+		_ => Err("Invalid tag length (should be 32/64/96/104/112/120/128)".to_string()),
+	}
+}
+
+fn check_ccm_params(nonce: &[u8], input: &[u8], tag_len: usize) -> Result<(), String> {
+	if !(7..=13).contains(&nonce.len()) {
+		return Err("Invalid nonce size (should be 7 ~ 13 bytes)".to_string());
+	}
+	let q = 15 - nonce.len();
+	if q < 8 && input.len() as u64 >= 1u64 << (8 * q) {
+		return Err("Input too long for the nonce size".to_string());
+	}
+	match tag_len {
+		4 | 6 | 8 | 10 | 12 | 14 | 16 => Ok(()),
+		_ => Err("Invalid tag length (should be 32/48/64/80/96/112/128)".to_string()),
+	}
+}
+
+fn split_tag(input: &[u8], tag_len: usize) -> Result<(&[u8], &[u8]), String> {
+	if input.len() < tag_len {
+		return Err("Invalid input (shorter than tag)".to_string());
+	}
+	Ok(input.split_at(input.len() - tag_len))
+}
+
+fn check_tag(expected: &[u8], actual: &[u8]) -> Result<(), String> {
+	let diff = expected
+		.iter()
+		.zip(actual)
+		.fold(0u8, |diff, (a, b)| diff | (a ^ b));
+	if expected.len() != actual.len() || diff != 0 {
+		return Err("Tag verification failed".to_string());
+	}
+	Ok(())
+}
+
+/// Encrypt (or decrypt) input in counter mode starting from the given counter block
+fn counter_process<F: Fn(&Block) -> Block>(
+	encrypt_block: &F,
+	counter: &Block,
+	input: &[u8],
+	inc: fn(&Block) -> Block,
+) -> Vec<u8> {
+	let mut counter = *counter;
+	let mut result = Vec::with_capacity(input.len());
+	for chunk in input.chunks(BLOCK_SIZE) {
+		let key_stream = encrypt_block(&counter);
+		result.extend(chunk.iter().zip(key_stream.iter()).map(|(a, b)| a ^ b));
+		counter = inc(&counter);
+	}
+	result
+}
+
+/// Increment the rightmost 32 bits
+fn inc32(block: &Block) -> Block {
+	let mut result = *block;
+	let mut counter = [0u8; 4];
+	counter.copy_from_slice(&block[12..]);
+	let counter = u32::from_be_bytes(counter).wrapping_add(1);
+	result[12..].copy_from_slice(&counter.to_be_bytes());
+	result
+}
+
+/// Increment the whole block
+fn inc_block(block: &Block) -> Block {
+	let counter = u128::from_be_bytes(*block).wrapping_add(1);
+	counter.to_be_bytes()
+}
+
+fn gcm_j0(h: &Block, nonce: &[u8]) -> Block {
+	if nonce.len() == 12 {
+		let mut j0 = [0u8; BLOCK_SIZE];
+		j0[..12].copy_from_slice(nonce);
+		j0[15] = 1;
+		j0
+	} else {
+		let mut data = pad_block(nonce);
+		data.extend(&[0u8; 8]);
+		data.extend(&(nonce.len() as u64 * 8).to_be_bytes());
+		ghash(h, &data)
+	}
+}
+
+fn gcm_tag<F: Fn(&Block) -> Block>(
+	encrypt_block: &F,
+	h: &Block,
+	j0: &Block,
+	aad: &[u8],
+	cipher: &[u8],
+	tag_len: usize,
+) -> Vec<u8> {
+	let mut data = pad_block(aad);
+	data.extend(pad_block(cipher));
+	data.extend(&(aad.len() as u64 * 8).to_be_bytes());
+	data.extend(&(cipher.len() as u64 * 8).to_be_bytes());
+	let s = ghash(h, &data);
+
+	let mut tag = counter_process(encrypt_block, j0, &s, inc32);
+	tag.truncate(tag_len);
+	tag
+}
+
+/// GHASH, data length should be a multiple of the block size
+fn ghash(h: &Block, data: &[u8]) -> Block {
+	let h = u128::from_be_bytes(*h);
+	let y = data.chunks(BLOCK_SIZE).fold(0u128, |y, chunk| {
+		gf_mul(y ^ u128::from_be_bytes(to_block(chunk)), h)
+	});
+	y.to_be_bytes()
+}
+
+/// Multiplication in GF(2^128)
+fn gf_mul(x: u128, y: u128) -> u128 {
+	const R: u128 = 0xe1 << 120;
+	let mut z = 0u128;
+	let mut v = y;
+	for i in 0..128 {
+		if (x >> (127 - i)) & 1 == 1 {
+			z ^= v;
+		}
+		v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+	}
+	z
+}
+
+fn ccm_ctr0(nonce: &[u8]) -> Block {
+	let q = 15 - nonce.len();
+	let mut ctr0 = [0u8; BLOCK_SIZE];
+	ctr0[0] = (q - 1) as u8;
+	ctr0[1..1 + nonce.len()].copy_from_slice(nonce);
+	ctr0
+}
+
+fn ccm_mac<F: Fn(&Block) -> Block>(
+	encrypt_block: &F,
+	nonce: &[u8],
+	aad: &[u8],
+	input: &[u8],
+	tag_len: usize,
+) -> Vec<u8> {
+	let q = 15 - nonce.len();
+
+	// B0
+	let flags =
+		(if aad.is_empty() { 0 } else { 0x40 }) | (((tag_len - 2) / 2) << 3) as u8 | (q - 1) as u8;
+	let mut data = vec![flags];
+	data.extend(nonce);
+	data.extend(&(input.len() as u64).to_be_bytes()[8 - q..]);
+
+	// associated data
+	if !aad.is_empty() {
+		let mut a = if aad.len() < 0xff00 {
+			(aad.len() as u16).to_be_bytes().to_vec()
+		} else if aad.len() as u64 <= u32::MAX as u64 {
+			[&[0xff, 0xfe][..], &(aad.len() as u32).to_be_bytes()].concat()
+		} else {
+			[&[0xff, 0xff][..], &(aad.len() as u64).to_be_bytes()].concat()
+		};
+		a.extend(aad);
+		data.extend(pad_block(&a));
+	}
+
+	// payload
+	data.extend(pad_block(input));
+
+	// CBC-MAC
+	let mac = data.chunks(BLOCK_SIZE).fold([0u8; BLOCK_SIZE], |y, chunk| {
+		encrypt_block(&xor_block(&y, chunk))
+	});
+
+	mac[..tag_len].to_vec()
+}
+
+/// Pad with zeros to a multiple of the block size
+fn pad_block(data: &[u8]) -> Vec<u8> {
+	let mut result = data.to_vec();
+	let padding = (BLOCK_SIZE - data.len() % BLOCK_SIZE) % BLOCK_SIZE;
+	result.resize(data.len() + padding, 0);
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_padding() {
+		let cases = vec![
+			(Padding::Pkcs7, "616263", "6162630d0d0d0d0d0d0d0d0d0d0d0d0d"),
+			(Padding::Zero, "616263", "61626300000000000000000000000000"),
+			(
+				Padding::Iso7816,
+				"616263",
+				"61626380000000000000000000000000",
+			),
+			(Padding::X923, "616263", "6162630000000000000000000000000d"),
+			(
+				Padding::Pkcs7,
+				"61626364616263646162636461626364",
+				"6162636461626364616263646162636410101010101010101010101010101010",
+			),
+			(
+				Padding::None,
+				"61626364616263646162636461626364",
+				"61626364616263646162636461626364",
+			),
+		];
+		for (padding, input, padded) in cases {
+			let input = hex::decode(input).unwrap();
+			let padded = hex::decode(padded).unwrap();
+			assert_eq!(padding.pad(&input), Ok(padded.clone()));
+			assert_eq!(padding.unpad(&padded), Ok(input));
+		}
+
+		assert!(Padding::None.pad(&[0x61]).is_err());
+		for padding in [Padding::Pkcs7, Padding::Iso7816, Padding::X923] {
+			assert_eq!(
+				padding.unpad(&[0u8; 16]),
+				Err("Invalid padding".to_string())
+			);
+		}
+		let mut invalid = [0u8; 16];
+		invalid[14] = 1;
+		invalid[15] = 2;
+		assert_eq!(
+			Padding::Pkcs7.unpad(&invalid),
+			Err("Invalid padding".to_string())
+		);
+		assert_eq!(
+			Padding::X923.unpad(&invalid),
+			Err("Invalid padding".to_string())
+		);
+	}
+}
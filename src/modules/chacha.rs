@@ -0,0 +1,507 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, Command, Module};
+use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use chacha20::ChaCha20;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+use clap::{Arg, ArgMatches, SubCommand};
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "ChaCha20 encrypt / decrypt (ChaCha20-Poly1305, XChaCha20-Poly1305)".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+const ALGORITHM_NAMES: [&str; 3] = ["chacha20", "chacha20-poly1305", "xchacha20-poly1305"];
+
+const ALGORITHM_HELP: &str = "Algorithm\nchacha20: ChaCha20 (RFC 8439)\nchacha20-poly1305: ChaCha20-Poly1305 (RFC 8439)\nxchacha20-poly1305: XChaCha20-Poly1305\n";
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("chacha_enc")
+				.about("ChaCha20 encrypt")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Plain (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algo")
+						.short("A")
+						.help(ALGORITHM_HELP)
+						.takes_value(true)
+						.possible_values(&ALGORITHM_NAMES)
+						.default_value("chacha20-poly1305")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.long("key")
+						.short("k")
+						.help("Key (Hex, 256 bits)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce (Hex, 96 bits, 192 bits for xchacha20-poly1305)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for chacha20-poly1305, xchacha20-poly1305 (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("COUNTER")
+						.long("counter")
+						.short("c")
+						.help("Initial block counter for chacha20")
+						.takes_value(true)
+						.default_value("0")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: chacha_enc,
+		},
+		Command {
+			app: SubCommand::with_name("chacha_dec")
+				.about("ChaCha20 decrypt")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Cipher (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algo")
+						.short("A")
+						.help(ALGORITHM_HELP)
+						.takes_value(true)
+						.possible_values(&ALGORITHM_NAMES)
+						.default_value("chacha20-poly1305")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.long("key")
+						.short("k")
+						.help("Key (Hex, 256 bits)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce (Hex, 96 bits, 192 bits for xchacha20-poly1305)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for chacha20-poly1305, xchacha20-poly1305 (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("COUNTER")
+						.long("counter")
+						.short("c")
+						.help("Initial block counter for chacha20")
+						.takes_value(true)
+						.default_value("0")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: chacha_dec,
+		},
+	]
+}
+
+enum Algorithm {
+	ChaCha20 { counter: u32 },
+	ChaCha20Poly1305 { aad: Vec<u8> },
+	XChaCha20Poly1305 { aad: Vec<u8> },
+}
+
+fn chacha_enc(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let (algorithm, key, nonce, input) = get_common_arg(matches)?;
+
+	// cipher
+	let result = match algorithm {
+		Algorithm::ChaCha20 { counter } => chacha20_process(&key, &nonce, counter, &input),
+		Algorithm::ChaCha20Poly1305 { aad } => ChaCha20Poly1305::new_from_slice(&key)
+			.map_err(|_| "Invalid key")?
+			.encrypt(
+				nonce.as_slice().into(),
+				Payload {
+					msg: &input,
+					aad: &aad,
+				},
+			)
+			.map_err(|_| "Enc failed".to_string()),
+		Algorithm::XChaCha20Poly1305 { aad } => XChaCha20Poly1305::new_from_slice(&key)
+			.map_err(|_| "Invalid key")?
+			.encrypt(
+				nonce.as_slice().into(),
+				Payload {
+					msg: &input,
+					aad: &aad,
+				},
+			)
+			.map_err(|_| "Enc failed".to_string()),
+	}?;
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn chacha_dec(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let (algorithm, key, nonce, input) = get_common_arg(matches)?;
+
+	// plain
+	let result = match algorithm {
+		Algorithm::ChaCha20 { counter } => chacha20_process(&key, &nonce, counter, &input),
+		Algorithm::ChaCha20Poly1305 { aad } => ChaCha20Poly1305::new_from_slice(&key)
+			.map_err(|_| "Invalid key")?
+			.decrypt(
+				nonce.as_slice().into(),
+				Payload {
+					msg: &input,
+					aad: &aad,
+				},
+			)
+			.map_err(|_| "Tag verification failed".to_string()),
+		Algorithm::XChaCha20Poly1305 { aad } => XChaCha20Poly1305::new_from_slice(&key)
+			.map_err(|_| "Invalid key")?
+			.decrypt(
+				nonce.as_slice().into(),
+				Payload {
+					msg: &input,
+					aad: &aad,
+				},
+			)
+			.map_err(|_| "Tag verification failed".to_string()),
+	}?;
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn get_common_arg(matches: &ArgMatches) -> Result<(Algorithm, Vec<u8>, Vec<u8>, Vec<u8>), String> {
+	let input = base::input_string(matches)?;
+
+	// key
+	let key = matches
+		.value_of("KEY")
+		.ok_or_else(|| "Invalid key".to_string())?;
+	let key: Vec<u8> = key.parse::<Hex>().map_err(|_| "Invalid key")?.into();
+	if key.len() != 32 {
+		return Err("Invalid key size (should be 256)".to_string());
+	}
+
+	let get_aad = || -> Result<Vec<u8>, String> {
+		let aad = matches
+			.value_of("AAD")
+			.ok_or_else(|| "Invalid AAD".to_string())?;
+		let aad: Vec<u8> = aad.parse::<Hex>().map_err(|_| "Invalid AAD")?.into();
+		Ok(aad)
+	};
+
+	let get_counter = || -> Result<u32, String> {
+		let counter = matches
+			.value_of("COUNTER")
+			.ok_or_else(|| "Invalid counter".to_string())?;
+		let counter = counter.parse::<u32>().map_err(|_| "Invalid counter")?;
+		Ok(counter)
+	};
+
+	// algorithm
+	let algorithm = matches
+		.value_of("ALGORITHM")
+		.ok_or_else(|| "Invalid algorithm".to_string())?;
+	let (algorithm, nonce_size) = match algorithm {
+		"chacha20" => (
+			Algorithm::ChaCha20 {
+				counter: get_counter()?,
+			},
+			12,
+		),
+		"chacha20-poly1305" => (Algorithm::ChaCha20Poly1305 { aad: get_aad()? }, 12),
+		"xchacha20-poly1305" => (Algorithm::XChaCha20Poly1305 { aad: get_aad()? }, 24),
+		_ => unreachable!(),
+	};
+
+	// nonce
+	let nonce = matches
+		.value_of("NONCE")
+		.ok_or_else(|| "Invalid nonce".to_string())?;
+	let nonce: Vec<u8> = nonce.parse::<Hex>().map_err(|_| "Invalid nonce")?.into();
+	if nonce.len() != nonce_size {
+		return Err(format!("Invalid nonce size (should be {})", nonce_size * 8));
+	}
+
+	// input
+	let input = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
+
+	Ok((algorithm, key, nonce, input))
+}
+
+fn chacha20_process(
+	key: &[u8],
+	nonce: &[u8],
+	counter: u32,
+	input: &[u8],
+) -> Result<Vec<u8>, String> {
+	let mut cipher = ChaCha20::new_from_slices(key, nonce).map_err(|_| "Invalid key or nonce")?;
+	cipher
+		.try_seek(counter as u64 * 64)
+		.map_err(|_| "Invalid counter")?;
+
+	let mut result = input.to_vec();
+	cipher
+		.try_apply_keystream(&mut result)
+		.map_err(|_| "Input too long for the counter")?;
+	Ok(result)
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"chacha_enc",
+				vec![
+					Case {
+						desc: "ChaCha20-Poly1305".to_string(),
+						input: vec![
+							"-k",
+							"0101010101010101010101010101010101010101010101010101010101010101",
+							"-n",
+							"020202020202020202020202",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x877e91e31e529f366477f29ce5cca679591415"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ChaCha20 (RFC 8439 2.4.2)".to_string(),
+						input: vec![
+							"-A",
+							"chacha20",
+							"-k",
+							"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+							"-n",
+							"000000000000004a00000000",
+							"-c",
+							"1",
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ChaCha20-Poly1305 (RFC 8439 2.8.2)".to_string(),
+						input: vec![
+							"-k",
+							"808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f",
+							"-n",
+							"070000004041424344454647",
+							"-a",
+							"0x50515253c0c1c2c3c4c5c6c7",
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xd31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "XChaCha20-Poly1305".to_string(),
+						input: vec![
+							"-A",
+							"xchacha20-poly1305",
+							"-k",
+							"808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f",
+							"-n",
+							"404142434445464748494a4b4c4d4e4f5051525354555657",
+							"-a",
+							"0x50515253c0c1c2c3c4c5c6c7",
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xbd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b4522f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff921f9664c97637da9768812f615c68b13b52ec0875924c1c7987947deafd8780acf49",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"chacha_dec",
+				vec![
+					Case {
+						desc: "ChaCha20-Poly1305".to_string(),
+						input: vec![
+							"-k",
+							"0101010101010101010101010101010101010101010101010101010101010101",
+							"-n",
+							"020202020202020202020202",
+							"0x877e91e31e529f366477f29ce5cca679591415",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ChaCha20 (RFC 8439 2.4.2)".to_string(),
+						input: vec![
+							"-A",
+							"chacha20",
+							"-k",
+							"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+							"-n",
+							"000000000000004a00000000",
+							"-c",
+							"1",
+							"0x6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ChaCha20-Poly1305 (RFC 8439 2.8.2)".to_string(),
+						input: vec![
+							"-k",
+							"808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f",
+							"-n",
+							"070000004041424344454647",
+							"-a",
+							"0x50515253c0c1c2c3c4c5c6c7",
+							"0xd31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "XChaCha20-Poly1305".to_string(),
+						input: vec![
+							"-A",
+							"xchacha20-poly1305",
+							"-k",
+							"808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f",
+							"-n",
+							"404142434445464748494a4b4c4d4e4f5051525354555657",
+							"-a",
+							"0x50515253c0c1c2c3c4c5c6c7",
+							"0xbd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b4522f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff921f9664c97637da9768812f615c68b13b52ec0875924c1c7987947deafd8780acf49",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
@@ -0,0 +1,858 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, Command, Module};
+use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::iter;
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Hex / base16 / base32 / base36 / base62 / base85 conversion".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+struct Base32Alphabet {
+	name: &'static str,
+	help: &'static str,
+	chars: &'static [u8; 32],
+	pad: bool,
+}
+
+struct Base85Alphabet {
+	name: &'static str,
+	help: &'static str,
+	chars: &'static [u8; 85],
+	/// Adobe Ascii85: `z` for a zero group, wrapped in `<~ ~>`
+	adobe: bool,
+	/// Whether the input length may not be a multiple of the group length
+	partial: bool,
+}
+
+const BASE36_CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+const BASE62_CHARS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+lazy_static! {
+	static ref RAW_BASE32_ALPHABETS: Vec<Base32Alphabet> = vec![
+		Base32Alphabet {
+			name: "standard",
+			help: "Standard (RFC 4648, A-Z2-7)",
+			chars: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+			pad: true,
+		},
+		Base32Alphabet {
+			name: "hex",
+			help: "Extended hex (RFC 4648 base32hex, 0-9A-V)",
+			chars: b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+			pad: true,
+		},
+		Base32Alphabet {
+			name: "crockford",
+			help: "Crockford (no padding, I / L read as 1, O as 0, hyphens ignored)",
+			chars: b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+			pad: false,
+		},
+	];
+	static ref BASE32_ALPHABETS: HashMap<&'static str, &'static Base32Alphabet> =
+		RAW_BASE32_ALPHABETS.iter().map(|x| (x.name, x)).collect();
+	static ref BASE32_ALPHABET_NAMES: Vec<&'static str> =
+		RAW_BASE32_ALPHABETS.iter().map(|x| x.name).collect();
+	static ref BASE32_ALPHABET_HELP: String = "Alphabet\n".to_string()
+		+ &RAW_BASE32_ALPHABETS
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+	static ref RAW_BASE85_ALPHABETS: Vec<Base85Alphabet> = vec![
+		Base85Alphabet {
+			name: "ascii85",
+			help: "Ascii85 (Adobe, <~ ~>)",
+			chars: b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+			adobe: true,
+			partial: true,
+		},
+		Base85Alphabet {
+			name: "rfc1924",
+			help: "RFC 1924 (Git, Python b85encode)",
+			chars: b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+			adobe: false,
+			partial: true,
+		},
+		Base85Alphabet {
+			name: "z85",
+			help: "Z85 (ZeroMQ, length multiple of 4)",
+			chars: b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+			adobe: false,
+			partial: false,
+		},
+	];
+	static ref BASE85_ALPHABETS: HashMap<&'static str, &'static Base85Alphabet> =
+		RAW_BASE85_ALPHABETS.iter().map(|x| (x.name, x)).collect();
+	static ref BASE85_ALPHABET_NAMES: Vec<&'static str> =
+		RAW_BASE85_ALPHABETS.iter().map(|x| x.name).collect();
+	static ref BASE85_ALPHABET_HELP: String = "Alphabet\n".to_string()
+		+ &RAW_BASE85_ALPHABETS
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+}
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("h2b16")
+				.about("Convert hex to base16 (RFC 4648)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
+			f: h2b16,
+		},
+		Command {
+			app: SubCommand::with_name("b162h")
+				.about("Convert base16 to hex")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
+			f: b162h,
+		},
+		Command {
+			app: SubCommand::with_name("h2b32")
+				.about("Convert hex to base32")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&BASE32_ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&BASE32_ALPHABET_NAMES)
+						.default_value("standard")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NO_PAD")
+						.long("no-pad")
+						.short("n")
+						.help("No padding")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: h2b32,
+		},
+		Command {
+			app: SubCommand::with_name("b322h")
+				.about("Convert base32 to hex (case insensitive, padding optional)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&BASE32_ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&BASE32_ALPHABET_NAMES)
+						.default_value("standard")
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
+			f: b322h,
+		},
+		Command {
+			app: SubCommand::with_name("h2b36")
+				.about("Convert hex to base36 (0-9a-z)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
+			f: h2b36,
+		},
+		Command {
+			app: SubCommand::with_name("b362h")
+				.about("Convert base36 to hex (case insensitive)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
+			f: b362h,
+		},
+		Command {
+			app: SubCommand::with_name("h2b62")
+				.about("Convert hex to base62 (0-9A-Za-z)")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
+			f: h2b62,
+		},
+		Command {
+			app: SubCommand::with_name("b622h")
+				.about("Convert base62 to hex")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
+			f: b622h,
+		},
+		Command {
+			app: SubCommand::with_name("h2b85")
+				.about("Convert hex to base85")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&BASE85_ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&BASE85_ALPHABET_NAMES)
+						.default_value("ascii85")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: h2b85,
+		},
+		Command {
+			app: SubCommand::with_name("b852h")
+				.about("Convert base85 to hex")
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(
+					Arg::with_name("ALPHABET")
+						.long("alphabet")
+						.short("a")
+						.help(&BASE85_ALPHABET_HELP)
+						.takes_value(true)
+						.possible_values(&BASE85_ALPHABET_NAMES)
+						.default_value("ascii85")
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
+			f: b852h,
+		},
+	]
+}
+
+fn h2b16(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let result = hex::encode_upper(input);
+
+	Ok(vec![result])
+}
+
+fn b162h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input = remove_whitespace(&input);
+
+	let result = hex::decode(input).map_err(|_| "Convert failed")?;
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn h2b32(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = BASE32_ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+
+	let mut result = String::new();
+	let (mut buffer, mut bits) = (0u16, 0);
+	for byte in input {
+		buffer = (buffer << 8) | byte as u16;
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			result.push(alphabet.chars[(buffer >> bits) as usize & 0x1f] as char);
+		}
+	}
+	if bits > 0 {
+		result.push(alphabet.chars[(buffer << (5 - bits)) as usize & 0x1f] as char);
+	}
+
+	if alphabet.pad && !matches.is_present("NO_PAD") {
+		while result.len() % 8 != 0 {
+			result.push('=');
+		}
+	}
+
+	Ok(vec![result])
+}
+
+fn b322h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = BASE32_ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+
+	let input = remove_whitespace(&input).to_uppercase();
+	let input = input.trim_end_matches('=');
+	let input = match alphabet.name {
+		"crockford" => input
+			.chars()
+			.filter(|c| *c != '-')
+			.map(|c| match c {
+				'I' | 'L' => '1',
+				'O' => '0',
+				c => c,
+			})
+			.collect(),
+		_ => input.to_string(),
+	};
+
+	// 1, 3 or 6 trailing chars can not come from whole bytes
+	if let 1 | 3 | 6 = input.len() % 8 {
+		return Err("Convert failed".to_string());
+	}
+
+	let mut result = vec![];
+	let (mut buffer, mut bits) = (0u16, 0);
+	for c in input.bytes() {
+		let value = alphabet
+			.chars
+			.iter()
+			.position(|x| *x == c)
+			.ok_or("Convert failed")?;
+		buffer = (buffer << 5) | value as u16;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			result.push((buffer >> bits) as u8);
+		}
+	}
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn h2b36(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let result = base_x_encode(&input, BASE36_CHARS);
+
+	Ok(vec![result])
+}
+
+fn b362h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input = remove_whitespace(&input).to_lowercase();
+
+	let result = base_x_decode(&input, BASE36_CHARS).ok_or("Convert failed")?;
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn h2b62(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let result = base_x_encode(&input, BASE62_CHARS);
+
+	Ok(vec![result])
+}
+
+fn b622h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input = remove_whitespace(&input);
+
+	let result = base_x_decode(&input, BASE62_CHARS).ok_or("Convert failed")?;
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn h2b85(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = BASE85_ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+
+	if !alphabet.partial && input.len() % 4 != 0 {
+		return Err("Convert failed: length should be a multiple of 4".to_string());
+	}
+
+	let mut result = String::new();
+	for chunk in input.chunks(4) {
+		let mut group = [0u8; 4];
+		group[..chunk.len()].copy_from_slice(chunk);
+		let mut value = u32::from_be_bytes(group);
+
+		if alphabet.adobe && chunk.len() == 4 && value == 0 {
+			result.push('z');
+			continue;
+		}
+
+		let mut digits = [0u8; 5];
+		for digit in digits.iter_mut().rev() {
+			*digit = alphabet.chars[(value % 85) as usize];
+			value /= 85;
+		}
+		result.extend(digits[..chunk.len() + 1].iter().map(|x| *x as char));
+	}
+
+	let result = match alphabet.adobe {
+		true => format!("<~{}~>", result),
+		false => result,
+	};
+
+	Ok(vec![result])
+}
+
+fn b852h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let alphabet = matches.value_of("ALPHABET").ok_or("Invalid alphabet")?;
+	let alphabet = BASE85_ALPHABETS.get(alphabet).ok_or("Invalid alphabet")?;
+
+	let input = remove_whitespace(&input);
+	let input = match alphabet.adobe {
+		true => input
+			.trim_start_matches("<~")
+			.trim_end_matches("~>")
+			.replace('z', "!!!!!"),
+		false => input,
+	};
+
+	if !alphabet.partial && input.len() % 5 != 0 {
+		return Err("Convert failed: length should be a multiple of 5".to_string());
+	}
+
+	let mut result = vec![];
+	for chunk in input.as_bytes().chunks(5) {
+		if chunk.len() == 1 {
+			return Err("Convert failed".to_string());
+		}
+		let mut value = 0u64;
+		for i in 0..5 {
+			// a partial group is padded with the last char
+			let c = chunk.get(i).unwrap_or(&alphabet.chars[84]);
+			let digit = alphabet
+				.chars
+				.iter()
+				.position(|x| x == c)
+				.ok_or("Convert failed")?;
+			value = value * 85 + digit as u64;
+		}
+		if value > u32::MAX as u64 {
+			return Err("Convert failed".to_string());
+		}
+		result.extend(&(value as u32).to_be_bytes()[..chunk.len() - 1]);
+	}
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+/// Big number base conversion, leading zero bytes kept as leading zero chars (like base58)
+fn base_x_encode(input: &[u8], chars: &[u8]) -> String {
+	let base = chars.len() as u32;
+
+	// little endian
+	let mut digits: Vec<u32> = vec![];
+	for byte in input {
+		let mut carry = *byte as u32;
+		for digit in digits.iter_mut() {
+			carry += *digit << 8;
+			*digit = carry % base;
+			carry /= base;
+		}
+		while carry > 0 {
+			digits.push(carry % base);
+			carry /= base;
+		}
+	}
+
+	let zeros = input.iter().take_while(|x| **x == 0).count();
+
+	iter::repeat(chars[0])
+		.take(zeros)
+		.chain(digits.iter().rev().map(|x| chars[*x as usize]))
+		.map(char::from)
+		.collect()
+}
+
+fn base_x_decode(input: &str, chars: &[u8]) -> Option<Vec<u8>> {
+	let base = chars.len() as u32;
+
+	// little endian
+	let mut bytes: Vec<u8> = vec![];
+	for c in input.bytes() {
+		let mut carry = chars.iter().position(|x| *x == c)? as u32;
+		for byte in bytes.iter_mut() {
+			carry += *byte as u32 * base;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+
+	let zeros = input.bytes().take_while(|x| *x == chars[0]).count();
+
+	Some(
+		iter::repeat(0)
+			.take(zeros)
+			.chain(bytes.into_iter().rev())
+			.collect(),
+	)
+}
+
+fn remove_whitespace(input: &str) -> String {
+	input.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"h2b16",
+				vec![Case {
+					desc: "RFC 4648".to_string(),
+					input: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+					output: vec!["666F6F626172"].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.13.0".to_string(),
+				}],
+			),
+			(
+				"b162h",
+				vec![Case {
+					desc: "RFC 4648".to_string(),
+					input: vec!["666F6F626172"].into_iter().map(Into::into).collect(),
+					output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.13.0".to_string(),
+				}],
+			),
+			(
+				"h2b32",
+				vec![
+					Case {
+						desc: "RFC 4648".to_string(),
+						input: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						output: vec!["MZXW6YTBOI======"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "base32hex (RFC 4648)".to_string(),
+						input: vec!["-a", "hex", "0x666f6f626172"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["CPNMUOJ1E8======"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Crockford".to_string(),
+						input: vec!["-a", "crockford", "0x666f6f626172"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["CSQPYRK1E8"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "TOTP secret (RFC 6238), no padding".to_string(),
+						input: vec!["-n", "0x3132333435363738393031323334353637383930"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Padding".to_string(),
+						input: vec!["0x66"].into_iter().map(Into::into).collect(),
+						output: vec!["MY======"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"b322h",
+				vec![
+					Case {
+						desc: "RFC 4648".to_string(),
+						input: vec!["MZXW6YTBOI======"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Lowercase, without padding".to_string(),
+						input: vec!["mzxw6ytboi"].into_iter().map(Into::into).collect(),
+						output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "base32hex (RFC 4648)".to_string(),
+						input: vec!["-a", "hex", "CPNMUOJ1E8======"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Crockford".to_string(),
+						input: vec!["-a", "crockford", "csqp-yrkl-e8"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"h2b36",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x00ff"].into_iter().map(Into::into).collect(),
+						output: vec!["073"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x3b18e512dba79e4c8300dd08aeb37f8e728b8dad"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["6wikijjff5rietzaame80uolcepupil"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"b362h",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["073"].into_iter().map(Into::into).collect(),
+						output: vec!["0x00ff"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Case insensitive".to_string(),
+						input: vec!["6WIKIJJFF5RIETZAAME80UOLCEPUPIL"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x3b18e512dba79e4c8300dd08aeb37f8e728b8dad"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"h2b62",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x00ff"].into_iter().map(Into::into).collect(),
+						output: vec!["047"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x3b18e512dba79e4c8300dd08aeb37f8e728b8dad"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["8QnVTRdvEc6Q6tGuyCKRASSRNKH"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"b622h",
+				vec![Case {
+					desc: "".to_string(),
+					input: vec!["047"].into_iter().map(Into::into).collect(),
+					output: vec!["0x00ff"].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.13.0".to_string(),
+				}],
+			),
+			(
+				"h2b85",
+				vec![
+					Case {
+						desc: "Ascii85".to_string(),
+						input: vec!["0x4d616e2069732064697374696e67756973686564"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["<~9jqo^BlbD-BleB1DJ+*+F(f,q~>"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Ascii85 zero group".to_string(),
+						input: vec!["0x0000000001"].into_iter().map(Into::into).collect(),
+						output: vec!["<~z!<~>"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "RFC 1924".to_string(),
+						input: vec!["-a", "rfc1924", "0x666f6f626172"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["W^Zp|VR8"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Z85 (ZeroMQ RFC 32)".to_string(),
+						input: vec!["-a", "z85", "0x864fd26fb559f75b"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["HelloWorld"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"b852h",
+				vec![
+					Case {
+						desc: "Ascii85".to_string(),
+						input: vec!["<~9jqo^BlbD-BleB1DJ+*+F(f,q~>"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x4d616e2069732064697374696e67756973686564"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Ascii85 zero group".to_string(),
+						input: vec!["<~z!<~>"].into_iter().map(Into::into).collect(),
+						output: vec!["0x0000000001"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "RFC 1924".to_string(),
+						input: vec!["-a", "rfc1924", "W^Zp|VR8"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x666f6f626172"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Z85 (ZeroMQ RFC 32)".to_string(),
+						input: vec!["-a", "z85", "HelloWorld"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x864fd26fb559f75b"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
@@ -1,7 +1,8 @@
 use crate::modules::base::Hex;
-use crate::modules::{base, Command, Module};
+use crate::modules::{base, key, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
 use lazy_static::lazy_static;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use std::collections::HashMap;
 
 mod p256;
@@ -20,11 +21,16 @@ pub fn module<'a, 'b>() -> Module<'a, 'b> {
 struct Curve {
 	name: &'static str,
 	help: &'static str,
+	/// Default hash algorithm
+	hash: &'static str,
+	/// Whether sign_f / verify_f / recover_f take the digest instead of the message
+	prehash: bool,
 	gk_f: fn(compress: bool) -> Result<(Vec<u8>, Vec<u8>), String>,
 	sign_f: fn(
 		secret_key: Vec<u8>,
 		message: Vec<u8>,
 		sig_form: SignatureFormEnum,
+		deterministic: bool,
 	) -> Result<Vec<u8>, String>,
 	verify_f: fn(
 		public_key: Vec<u8>,
@@ -33,12 +39,33 @@ struct Curve {
 		sig_form: SignatureFormEnum,
 	) -> Result<(), String>,
 	pk_f: fn(secret_key: Vec<u8>, compress: bool) -> Result<Vec<u8>, String>,
+	recover_f: fn(
+		sig: Vec<u8>,
+		message: Vec<u8>,
+		recovery_id: Option<u8>,
+		compress: bool,
+	) -> Result<Vec<u8>, String>,
 }
 
 #[derive(Clone)]
 pub enum SignatureFormEnum {
 	Der,
 	Fixed,
+	Recoverable,
+}
+
+struct Hash {
+	name: &'static str,
+	help: &'static str,
+	f: fn(data: &[u8]) -> Vec<u8>,
+}
+
+struct Prefix {
+	name: &'static str,
+	help: &'static str,
+	f: fn(message: &[u8]) -> Vec<u8>,
+	/// Default hash algorithm of the scheme
+	hash: Option<&'static str>,
 }
 
 struct SignatureForm {
@@ -52,34 +79,46 @@ lazy_static! {
 		Curve {
 			name: "secp256k1",
 			help: "Secp256k1",
+			hash: "sha256",
+			prehash: true,
 			gk_f: secp256k1::ec_gk_secp256k1,
 			sign_f: secp256k1::ec_sign_secp256k1,
 			verify_f: secp256k1::ec_verify_secp256k1,
 			pk_f: secp256k1::ec_pk_secp256k1,
+			recover_f: secp256k1::ec_recover_secp256k1,
 		},
 		Curve {
 			name: "p256",
 			help: "NIST P-256",
+			hash: "sha256",
+			prehash: true,
 			gk_f: p256::ec_gk_p256,
 			sign_f: p256::ec_sign_p256,
 			verify_f: p256::ec_verify_p256,
 			pk_f: p256::ec_pk_p256,
+			recover_f: p256::ec_recover_p256,
 		},
 		Curve {
 			name: "p384",
 			help: "NIST P-384",
+			hash: "sha384",
+			prehash: true,
 			gk_f: p384::ec_gk_p384,
 			sign_f: p384::ec_sign_p384,
 			verify_f: p384::ec_verify_p384,
 			pk_f: p384::ec_pk_p384,
+			recover_f: p384::ec_recover_p384,
 		},
 		Curve {
 			name: "sm2",
 			help: "Chinese National Standard SM2",
+			hash: "sm3",
+			prehash: false,
 			gk_f: sm2::ec_gk_sm2,
 			sign_f: sm2::ec_sign_sm2,
 			verify_f: sm2::ec_verify_sm2,
 			pk_f: sm2::ec_pk_sm2,
+			recover_f: sm2::ec_recover_sm2,
 		},
 	];
 	static ref RAW_SIGNATURE_FORMS: Vec<SignatureForm> = vec![
@@ -93,6 +132,68 @@ lazy_static! {
 			help: "Fixed",
 			e: SignatureFormEnum::Fixed,
 		},
+		SignatureForm {
+			name: "recoverable",
+			help: "Recoverable (r || s || v with the raw recovery id 0 - 3 as v, secp256k1 only)",
+			e: SignatureFormEnum::Recoverable,
+		},
+	];
+	static ref RAW_HASHES: Vec<Hash> = vec![
+		Hash {
+			name: "none",
+			help: "None (the message is the digest)",
+			f: |data| data.to_vec(),
+		},
+		Hash {
+			name: "sha256",
+			help: "SHA-256",
+			f: |data| Sha256::digest(data).to_vec(),
+		},
+		Hash {
+			name: "sha256d",
+			help: "Double SHA-256",
+			f: |data| Sha256::digest(&Sha256::digest(data)).to_vec(),
+		},
+		Hash {
+			name: "sha384",
+			help: "SHA-384",
+			f: |data| Sha384::digest(data).to_vec(),
+		},
+		Hash {
+			name: "sha512",
+			help: "SHA-512",
+			f: |data| Sha512::digest(data).to_vec(),
+		},
+		Hash {
+			name: "keccak256",
+			help: "Keccak-256",
+			f: |data| sha3::Keccak256::digest(data).to_vec(),
+		},
+		Hash {
+			name: "sm3",
+			help: "Chinese National Standard SM3",
+			f: |data| <sm3::Sm3 as sm3::Digest>::digest(data).to_vec(),
+		},
+	];
+	static ref RAW_PREFIXES: Vec<Prefix> = vec![
+		Prefix {
+			name: "none",
+			help: "None",
+			f: |message| message.to_vec(),
+			hash: None,
+		},
+		Prefix {
+			name: "ethereum",
+			help: "Ethereum personal_sign (\\x19Ethereum Signed Message:\\n + length, default hash: keccak256)",
+			f: ethereum_prefix,
+			hash: Some("keccak256"),
+		},
+		Prefix {
+			name: "bitcoin",
+			help: "Bitcoin signed message (\\x18Bitcoin Signed Message:\\n + varint length, default hash: sha256d)",
+			f: bitcoin_prefix,
+			hash: Some("sha256d"),
+		},
 	];
 	static ref CURVES: HashMap<&'static str, &'static Curve> =
 		RAW_CURVES.iter().map(|x| (x.name, x)).collect();
@@ -104,6 +205,29 @@ lazy_static! {
 			.collect::<Vec<String>>()
 			.join("\n")
 		+ "\n";
+	static ref HASHES: HashMap<&'static str, &'static Hash> =
+		RAW_HASHES.iter().map(|x| (x.name, x)).collect();
+	static ref HASH_NAMES: Vec<&'static str> = RAW_HASHES.iter().map(|x| x.name).collect();
+	static ref HASH_HELP: String = "Hash algorithm applied to the (prefixed) message\n".to_string()
+		+ "Default: the prefix scheme's hash, otherwise the curve's hash \
+		   (secp256k1: sha256, p256: sha256, p384: sha384, sm2: sm3)\n"
+		+ "sm2 only supports sm3 (computed internally with the signer's Z value)\n"
+		+ &RAW_HASHES
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+	static ref PREFIXES: HashMap<&'static str, &'static Prefix> =
+		RAW_PREFIXES.iter().map(|x| (x.name, x)).collect();
+	static ref PREFIX_NAMES: Vec<&'static str> = RAW_PREFIXES.iter().map(|x| x.name).collect();
+	static ref PREFIX_HELP: String = "Message prefix scheme\n".to_string()
+		+ &RAW_PREFIXES
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
 	static ref SIGNATURE_FORMS: HashMap<&'static str, &'static SignatureForm> =
 		RAW_SIGNATURE_FORMS.iter().map(|x| (x.name, x)).collect();
 	static ref SIGNATURE_FORM_NAMES: Vec<&'static str> =
@@ -137,6 +261,18 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.short("C")
 						.help("Compress")
 						.required(false),
+				)
+				.arg(
+					Arg::with_name("FORMAT")
+						.long("format")
+						.short("f")
+						.help(
+							"Format\nraw: (Secret key, Public key)\njwk: Private JWK (RFC 7517)\n",
+						)
+						.takes_value(true)
+						.possible_values(&["raw", "jwk"])
+						.default_value("raw")
+						.required(false),
 				),
 			f: ec_gk,
 		},
@@ -175,7 +311,35 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.possible_values(&SIGNATURE_FORM_NAMES)
 						.default_value("fixed")
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("DETERMINISTIC")
+						.long("deterministic")
+						.short("d")
+						.help("Deterministic nonce (RFC 6979, HMAC with the curve's hash)")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.short("H")
+						.help(&HASH_HELP)
+						.takes_value(true)
+						.possible_values(&HASH_NAMES)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.short("P")
+						.help(&PREFIX_HELP)
+						.takes_value(true)
+						.possible_values(&PREFIX_NAMES)
+						.default_value("none")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: ec_sign,
 		},
 		Command {
@@ -221,7 +385,27 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.possible_values(&SIGNATURE_FORM_NAMES)
 						.default_value("fixed")
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.short("H")
+						.help(&HASH_HELP)
+						.takes_value(true)
+						.possible_values(&HASH_NAMES)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.short("P")
+						.help(&PREFIX_HELP)
+						.takes_value(true)
+						.possible_values(&PREFIX_NAMES)
+						.default_value("none")
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
 			f: ec_verify,
 		},
 		Command {
@@ -250,9 +434,74 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.short("C")
 						.help("Compress")
 						.required(false),
-				),
+				)
+				.arg(base::out_format_arg()),
 			f: ec_pk,
 		},
+		Command {
+			app: SubCommand::with_name("ec_recover")
+				.about("Elliptic-curve recover public key")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Message (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("CURVE")
+						.long("curve")
+						.short("c")
+						.help(&CURVE_HELP)
+						.takes_value(true)
+						.possible_values(&CURVE_NAMES)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("SIGNATURE")
+						.long("sig")
+						.short("S")
+						.help("Signature (Hex, r || s || v, or r || s with --recovery-id)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("RECOVERY_ID")
+						.long("recovery-id")
+						.short("r")
+						.help("Recovery id (0 - 3, 27 - 30 for Ethereum, 31 - 34 for Bitcoin)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("COMPRESS")
+						.long("compress")
+						.short("C")
+						.help("Compress")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.short("H")
+						.help(&HASH_HELP)
+						.takes_value(true)
+						.possible_values(&HASH_NAMES)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.short("P")
+						.help(&PREFIX_HELP)
+						.takes_value(true)
+						.possible_values(&PREFIX_NAMES)
+						.default_value("none")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: ec_recover,
+		},
 	]
 }
 
@@ -265,6 +514,10 @@ fn ec_gk(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let (private_key, public_key) = (curve.gk_f)(compress)?;
 
+	if matches.value_of("FORMAT") == Some("jwk") {
+		return Ok(vec![key::to_jwk(curve.name, private_key)?]);
+	}
+
 	let (private_key, public_key): (String, String) =
 		(Hex::from(private_key).into(), Hex::from(public_key).into());
 
@@ -296,7 +549,11 @@ fn ec_sign(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.e
 		.clone();
 
-	let sig = (curve.sign_f)(secret_key, input, sig_form)?;
+	let deterministic = matches.is_present("DETERMINISTIC");
+
+	let input = digest(curve, matches, input)?;
+
+	let sig = (curve.sign_f)(secret_key, input, sig_form, deterministic)?;
 
 	let result = Hex::from(sig).into();
 
@@ -329,6 +586,8 @@ fn ec_verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
 
+	let input = digest(curve, matches, input)?;
+
 	(curve.verify_f)(public_key, sig, input, sig_form)?;
 
 	let result = "true".to_string();
@@ -356,6 +615,110 @@ fn ec_pk(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	Ok(vec![result])
 }
 
+fn ec_recover(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let curve = matches.value_of("CURVE").ok_or("Invalid curve")?;
+
+	let curve = CURVES.get(curve).ok_or("Invalid curve")?;
+
+	let sig = matches.value_of("SIGNATURE").ok_or("Invalid signature")?;
+	let sig: Vec<u8> = sig.parse::<Hex>().map_err(|_| "Invalid signature")?.into();
+
+	let recovery_id = match matches.value_of("RECOVERY_ID") {
+		Some(v) => Some(v.parse::<u8>().map_err(|_| "Invalid recovery id")?),
+		None => None,
+	};
+
+	let compress = matches.is_present("COMPRESS");
+
+	let input = base::input_string(matches)?;
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
+
+	let input = digest(curve, matches, input)?;
+
+	let public_key = (curve.recover_f)(sig, input, recovery_id, compress)?;
+
+	let result = Hex::from(public_key).into();
+
+	Ok(vec![result])
+}
+
+/// Sign the message with the curve's default hash (fixed form)
+pub fn sign(curve: &str, secret_key: Vec<u8>, message: Vec<u8>) -> Result<Vec<u8>, String> {
+	let curve = CURVES.get(curve).ok_or("Invalid curve")?;
+
+	let message = default_digest(curve, message)?;
+
+	(curve.sign_f)(secret_key, message, SignatureFormEnum::Fixed, false)
+}
+
+/// Verify the fixed form signature of the message with the curve's default hash
+pub fn verify(
+	curve: &str,
+	public_key: Vec<u8>,
+	sig: Vec<u8>,
+	message: Vec<u8>,
+) -> Result<(), String> {
+	let curve = CURVES.get(curve).ok_or("Invalid curve")?;
+
+	let message = default_digest(curve, message)?;
+
+	(curve.verify_f)(public_key, sig, message, SignatureFormEnum::Fixed)
+}
+
+fn default_digest(curve: &Curve, message: Vec<u8>) -> Result<Vec<u8>, String> {
+	let hash = HASHES.get(curve.hash).ok_or("Invalid hash")?;
+
+	match curve.prehash {
+		true => Ok((hash.f)(&message)),
+		false => Ok(message),
+	}
+}
+
+/// Apply the prefix scheme and the hash to the message
+/// For curves hashing internally (sm2), only the prefix is applied
+fn digest(curve: &Curve, matches: &ArgMatches, message: Vec<u8>) -> Result<Vec<u8>, String> {
+	let prefix = matches.value_of("PREFIX").ok_or("Invalid prefix")?;
+	let prefix = PREFIXES.get(prefix).ok_or("Invalid prefix")?;
+
+	let hash = matches
+		.value_of("HASH")
+		.or(prefix.hash)
+		.unwrap_or(curve.hash);
+	let hash = HASHES.get(hash).ok_or("Invalid hash")?;
+
+	let message = (prefix.f)(&message);
+
+	match curve.prehash {
+		true => Ok((hash.f)(&message)),
+		false if hash.name == curve.hash => Ok(message),
+		false => Err(format!("Hash {} is not supported", hash.name)),
+	}
+}
+
+fn ethereum_prefix(message: &[u8]) -> Vec<u8> {
+	let mut result = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	result.extend(message);
+	result
+}
+
+fn bitcoin_prefix(message: &[u8]) -> Vec<u8> {
+	let mut result = b"\x18Bitcoin Signed Message:\n".to_vec();
+	let len = message.len();
+	match len {
+		0..=0xfc => result.push(len as u8),
+		0xfd..=0xffff => {
+			result.push(0xfd);
+			result.extend(&(len as u16).to_le_bytes());
+		}
+		_ => {
+			result.push(0xfe);
+			result.extend(&(len as u32).to_le_bytes());
+		}
+	}
+	result.extend(message);
+	result
+}
+
 mod cases {
 	use super::p256;
 	use super::p384;
@@ -1,22 +1,16 @@
 use crate::modules::ecdsa::SignatureFormEnum;
 use crate::modules::Case;
 use linked_hash_map::LinkedHashMap;
+use p256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier, RandomizedPrehashSigner};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
 use p256::{elliptic_curve::sec1::ToEncodedPoint, SecretKey};
 use rand::thread_rng;
-use ring::{
-	rand::SystemRandom,
-	signature::{
-		EcdsaKeyPair, VerificationAlgorithm, ECDSA_P256_SHA256_ASN1,
-		ECDSA_P256_SHA256_ASN1_SIGNING, ECDSA_P256_SHA256_FIXED, ECDSA_P256_SHA256_FIXED_SIGNING,
-	},
-};
-use untrusted::Input;
 
 pub fn ec_gk_p256(compress: bool) -> Result<(Vec<u8>, Vec<u8>), String> {
 	let secret_key = SecretKey::random(&mut thread_rng());
 	let public_key = secret_key.public_key();
 
-	let secret_key = secret_key.as_scalar_bytes().as_bytes().as_slice().to_vec();
+	let secret_key = secret_key.to_bytes().to_vec();
 	let public_key = public_key.to_encoded_point(compress).as_bytes().to_vec();
 
 	Ok((secret_key, public_key))
@@ -24,58 +18,69 @@ pub fn ec_gk_p256(compress: bool) -> Result<(Vec<u8>, Vec<u8>), String> {
 
 pub fn ec_sign_p256(
 	secret_key: Vec<u8>,
-	message: Vec<u8>,
+	digest: Vec<u8>,
 	sig_form: SignatureFormEnum,
+	deterministic: bool,
 ) -> Result<Vec<u8>, String> {
-	let secret_key_obj = SecretKey::from_bytes(&secret_key).map_err(|_| "Invalid secret key")?;
-	let public_key = secret_key_obj.public_key();
-	let public_key = public_key.to_encoded_point(false);
-	let public_key = public_key.as_bytes();
+	let signing_key = SigningKey::from_slice(&secret_key).map_err(|_| "Invalid secret key")?;
 
-	let algo = match sig_form {
-		SignatureFormEnum::Fixed => &ECDSA_P256_SHA256_FIXED_SIGNING,
-		SignatureFormEnum::Der => &ECDSA_P256_SHA256_ASN1_SIGNING,
-	};
+	let sig: Signature = match deterministic {
+		true => signing_key.sign_prehash(&digest),
+		false => signing_key.sign_prehash_with_rng(&mut thread_rng(), &digest),
+	}
+	.map_err(|e| format!("Failed to sign: {}", e))?;
 
-	let pair = EcdsaKeyPair::from_private_key_and_public_key(&algo, &secret_key, public_key)
-		.map_err(|_| "Invalid secret key")?;
-	let sig = pair
-		.sign(&SystemRandom::new(), &message)
-		.map_err(|_| "Failed to sign")?;
+	let sig = match sig_form {
+		SignatureFormEnum::Fixed => sig.to_bytes().to_vec(),
+		SignatureFormEnum::Der => sig.to_der().as_bytes().to_vec(),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+	};
 
-	Ok(sig.as_ref().to_vec())
+	Ok(sig)
 }
 
 pub fn ec_verify_p256(
 	public_key: Vec<u8>,
 	sig: Vec<u8>,
-	message: Vec<u8>,
+	digest: Vec<u8>,
 	sig_form: SignatureFormEnum,
 ) -> Result<(), String> {
-	let algo = match sig_form {
-		SignatureFormEnum::Fixed => &ECDSA_P256_SHA256_FIXED,
-		SignatureFormEnum::Der => &ECDSA_P256_SHA256_ASN1,
-	};
+	let verifying_key =
+		VerifyingKey::from_sec1_bytes(&public_key).map_err(|_| "Invalid public key")?;
 
-	let result = algo
-		.verify(
-			Input::from(&public_key),
-			Input::from(&message),
-			Input::from(&sig),
-		)
-		.map_err(|e| format!("Invalid signature: {}", e))?;
+	let sig = match sig_form {
+		SignatureFormEnum::Fixed => Signature::from_slice(&sig),
+		SignatureFormEnum::Der => Signature::from_der(&sig),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+	}
+	.map_err(|e| format!("Invalid signature: {}", e))?;
 
-	Ok(result)
+	verifying_key
+		.verify_prehash(&digest, &sig)
+		.map_err(|e| format!("Invalid signature: {}", e))
 }
 
 pub fn ec_pk_p256(secret_key: Vec<u8>, compress: bool) -> Result<Vec<u8>, String> {
-	let secret_key_obj = SecretKey::from_bytes(&secret_key).map_err(|_| "Invalid secret key")?;
+	let secret_key_obj = SecretKey::from_slice(&secret_key).map_err(|_| "Invalid secret key")?;
 	let public_key = secret_key_obj.public_key();
 	let public_key = public_key.to_encoded_point(compress);
 	let public_key = public_key.as_bytes().to_vec();
 	Ok(public_key)
 }
 
+pub fn ec_recover_p256(
+	_sig: Vec<u8>,
+	_message: Vec<u8>,
+	_recovery_id: Option<u8>,
+	_compress: bool,
+) -> Result<Vec<u8>, String> {
+	Err("Recovery is not supported".to_string())
+}
+
 pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 	vec![
 		("ec_gk",
@@ -107,6 +112,38 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 				 is_test: false,
 				 since: "0.7.0".to_string(),
 			 },
+			 Case {
+				 desc: "P-256 deterministic (RFC 6979 A.2.5, message: sample)".to_string(),
+				 input: vec!["-c", "p256", "-s", "0xc9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721", "-d", "0x73616d706c65"].into_iter().map(Into::into).collect(),
+				 output: vec!["0xefd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"].into_iter().map(Into::into).collect(),
+				 is_example: true,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
+			 Case {
+				 desc: "P-256 deterministic (RFC 6979 A.2.5, message: test)".to_string(),
+				 input: vec!["-c", "p256", "-s", "0xc9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721", "-d", "0x74657374"].into_iter().map(Into::into).collect(),
+				 output: vec!["0xf1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
+			 Case {
+				 desc: "P-256 deterministic DER signature form".to_string(),
+				 input: vec!["-c", "p256", "-s", "0xc9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721", "-d", "-f", "der", "0x73616d706c65"].into_iter().map(Into::into).collect(),
+				 output: vec!["0x3046022100efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716022100f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
+			 Case {
+				 desc: "P-256 deterministic with the message digest as input".to_string(),
+				 input: vec!["-c", "p256", "-s", "0xc9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721", "-d", "-H", "none", "0xaf2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf"].into_iter().map(Into::into).collect(),
+				 output: vec!["0xefd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
 		 ]),
 		("ec_verify",
 		 vec![
@@ -130,6 +167,14 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 				 is_test: true,
 				 since: "0.7.0".to_string(),
 			 },
+			 Case {
+				 desc: "P-256 SHA-512".to_string(),
+				 input: vec!["-c", "p256", "-p", "0x0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299", "-H", "sha512", "-S", "0x962705d612647b04822c6060f31270f4b4cd703f6ba8fc1308c2a562ee600fc0af713dc0b1a1423422198a0edbce3b096f25c8e47d80988880ff472e579ab61f", "0x73616d706c65"].into_iter().map(Into::into).collect(),
+				 output: vec!["true"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
 		 ]),
 		("ec_pk",
 		 vec![
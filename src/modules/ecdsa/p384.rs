@@ -1,45 +1,84 @@
 use crate::modules::ecdsa::SignatureFormEnum;
 use crate::modules::Case;
 use linked_hash_map::LinkedHashMap;
-use ring::signature::{VerificationAlgorithm, ECDSA_P384_SHA384_ASN1, ECDSA_P384_SHA384_FIXED};
-use untrusted::Input;
+use p384::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier, RandomizedPrehashSigner};
+use p384::ecdsa::{Signature, SigningKey, VerifyingKey};
+use p384::{elliptic_curve::sec1::ToEncodedPoint, SecretKey};
+use rand::thread_rng;
 
-pub fn ec_gk_p384(_compress: bool) -> Result<(Vec<u8>, Vec<u8>), String> {
-	unimplemented!()
+pub fn ec_gk_p384(compress: bool) -> Result<(Vec<u8>, Vec<u8>), String> {
+	let secret_key = SecretKey::random(&mut thread_rng());
+	let public_key = secret_key.public_key();
+
+	let secret_key = secret_key.to_bytes().to_vec();
+	let public_key = public_key.to_encoded_point(compress).as_bytes().to_vec();
+
+	Ok((secret_key, public_key))
 }
 
 pub fn ec_sign_p384(
-	_secret_key: Vec<u8>,
-	_message: Vec<u8>,
-	_sig_form: SignatureFormEnum,
+	secret_key: Vec<u8>,
+	digest: Vec<u8>,
+	sig_form: SignatureFormEnum,
+	deterministic: bool,
 ) -> Result<Vec<u8>, String> {
-	unimplemented!()
+	let signing_key = SigningKey::from_slice(&secret_key).map_err(|_| "Invalid secret key")?;
+
+	let sig: Signature = match deterministic {
+		true => signing_key.sign_prehash(&digest),
+		false => signing_key.sign_prehash_with_rng(&mut thread_rng(), &digest),
+	}
+	.map_err(|e| format!("Failed to sign: {}", e))?;
+
+	let sig = match sig_form {
+		SignatureFormEnum::Fixed => sig.to_bytes().to_vec(),
+		SignatureFormEnum::Der => sig.to_der().as_bytes().to_vec(),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+	};
+
+	Ok(sig)
 }
 
 pub fn ec_verify_p384(
 	public_key: Vec<u8>,
 	sig: Vec<u8>,
-	message: Vec<u8>,
+	digest: Vec<u8>,
 	sig_form: SignatureFormEnum,
 ) -> Result<(), String> {
-	let algo = match sig_form {
-		SignatureFormEnum::Fixed => &ECDSA_P384_SHA384_FIXED,
-		SignatureFormEnum::Der => &ECDSA_P384_SHA384_ASN1,
-	};
+	let verifying_key =
+		VerifyingKey::from_sec1_bytes(&public_key).map_err(|_| "Invalid public key")?;
 
-	let result = algo
-		.verify(
-			Input::from(&public_key),
-			Input::from(&message),
-			Input::from(&sig),
-		)
-		.map_err(|e| format!("Invalid signature: {}", e))?;
+	let sig = match sig_form {
+		SignatureFormEnum::Fixed => Signature::from_slice(&sig),
+		SignatureFormEnum::Der => Signature::from_der(&sig),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+	}
+	.map_err(|e| format!("Invalid signature: {}", e))?;
 
-	Ok(result)
+	verifying_key
+		.verify_prehash(&digest, &sig)
+		.map_err(|e| format!("Invalid signature: {}", e))
 }
 
-pub fn ec_pk_p384(_secret_key: Vec<u8>, _compress: bool) -> Result<Vec<u8>, String> {
-	unimplemented!()
+pub fn ec_pk_p384(secret_key: Vec<u8>, compress: bool) -> Result<Vec<u8>, String> {
+	let secret_key_obj = SecretKey::from_slice(&secret_key).map_err(|_| "Invalid secret key")?;
+	let public_key = secret_key_obj.public_key();
+	let public_key = public_key.to_encoded_point(compress);
+	let public_key = public_key.as_bytes().to_vec();
+	Ok(public_key)
+}
+
+pub fn ec_recover_p384(
+	_sig: Vec<u8>,
+	_message: Vec<u8>,
+	_recovery_id: Option<u8>,
+	_compress: bool,
+) -> Result<Vec<u8>, String> {
+	Err("Recovery is not supported".to_string())
 }
 
 pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
@@ -73,6 +112,22 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 				 is_test: false,
 				 since: "0.7.0".to_string(),
 			 },
+			 Case {
+				 desc: "P-384 deterministic (RFC 6979 A.2.6, message: sample)".to_string(),
+				 input: vec!["-c", "p384", "-s", "0x6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5", "-d", "0x73616d706c65"].into_iter().map(Into::into).collect(),
+				 output: vec!["0x94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c81a648152e44acf96e36dd1e80fabe4699ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94fa329c145786e679e7b82c71a38628ac8"].into_iter().map(Into::into).collect(),
+				 is_example: true,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
+			 Case {
+				 desc: "P-384 deterministic (RFC 6979 A.2.6, message: test)".to_string(),
+				 input: vec!["-c", "p384", "-s", "0x6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5", "-d", "0x74657374"].into_iter().map(Into::into).collect(),
+				 output: vec!["0x8203b63d3c853e8d77227fb377bcf7b7b772e97892a80f36ab775d509d7a5feb0542a7f0812998da8f1dd3ca3cf023dbddd0760448d42d8a43af45af836fce4de8be06b485e9b61b827c2f13173923e06a739f040649a667bf3b828246baa5a5"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
 		 ]),
 		("ec_verify",
 		 vec![
@@ -96,6 +151,16 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 				 is_test: true,
 				 since: "0.7.0".to_string(),
 			 },
+			 Case {
+				 desc: "P-384 Compressed public key".to_string(),
+				 input: vec!["-c", "p384", "-p", "0x034978c6c7be1a5c5194983a945d2d8c81ae4b421dd89d12c6dd1756d2387fa2601993657eeb93d289a57625a70c2830db", "-S",
+				             "0xa0d387bc5d5de4979750f531f337fd1d04384ab4a9d251a18852c1ce1a16e2e46a2778764d0b3ee090babbc5092ea57a108ddabf9a9fcf8efaad7c0862da2beddde806745c0c3972d738c416d55cfde19b85e39ab54151c87b537c4df7d177ff",
+				             "0x616263"].into_iter().map(Into::into).collect(),
+				 output: vec!["true"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
 		 ]),
 		("ec_pk",
 		 vec![
@@ -104,9 +169,17 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 				 input: vec!["-c", "p384", "-s", "0xfbc89e8fae9340747f162330345f7cfac7387a2049f6bedb55f7a30faf8b1d24da9b1e618db7b215daa1c7b0fd54858f"].into_iter().map(Into::into).collect(),
 				 output: vec!["0x044978c6c7be1a5c5194983a945d2d8c81ae4b421dd89d12c6dd1756d2387fa2601993657eeb93d289a57625a70c2830db5f06f988a3e4549e26e8b6d27c7f1e6e8949d6ce5bf3f88a0f5eebaa14499d4379bc81cca6e9ff17d18b8efb370fffe3"].into_iter().map(Into::into).collect(),
 				 is_example: false,
-				 is_test: false,
+				 is_test: true,
 				 since: "0.7.0".to_string(),
 			 },
+			 Case {
+				 desc: "P-384 Compressed".to_string(),
+				 input: vec!["-c", "p384", "-s", "0xfbc89e8fae9340747f162330345f7cfac7387a2049f6bedb55f7a30faf8b1d24da9b1e618db7b215daa1c7b0fd54858f", "-C"].into_iter().map(Into::into).collect(),
+				 output: vec!["0x034978c6c7be1a5c5194983a945d2d8c81ae4b421dd89d12c6dd1756d2387fa2601993657eeb93d289a57625a70c2830db"].into_iter().map(Into::into).collect(),
+				 is_example: false,
+				 is_test: true,
+				 since: "0.13.0".to_string(),
+			 },
 		 ]),
 	].into_iter().collect()
 }
@@ -1,6 +1,6 @@
 use linked_hash_map::LinkedHashMap;
-use secp256k1::bitcoin_hashes::sha256;
 use secp256k1::rand::thread_rng;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
 use secp256k1::{Message, PublicKey, SecretKey, Signature};
 
 use crate::modules::base::Hex;
@@ -25,17 +25,34 @@ pub fn ec_gk_secp256k1(compress: bool) -> Result<(Vec<u8>, Vec<u8>), String> {
 
 pub fn ec_sign_secp256k1(
 	secret_key: Vec<u8>,
-	message: Vec<u8>,
+	digest: Vec<u8>,
 	sig_form: SignatureFormEnum,
+	_deterministic: bool,
 ) -> Result<Vec<u8>, String> {
-	let message = Message::from_hashed_data::<sha256::Hash>(&message);
+	// libsecp256k1 always derives the nonce with RFC 6979
+	let message = to_message(&digest)?;
 	let secret_key =
 		SecretKey::from_slice(&secret_key).map_err(|e| format!("Invalid secret key: {}", e))?;
-	let signature = secp256k1::Secp256k1::signing_only().sign(&message, &secret_key);
+	let secp = secp256k1::Secp256k1::signing_only();
 
 	let signature = match sig_form {
-		SignatureFormEnum::Fixed => signature.serialize_compact().to_vec(),
-		SignatureFormEnum::Der => signature.serialize_der().as_ref().to_vec(),
+		SignatureFormEnum::Fixed => secp
+			.sign(&message, &secret_key)
+			.serialize_compact()
+			.to_vec(),
+		SignatureFormEnum::Der => secp
+			.sign(&message, &secret_key)
+			.serialize_der()
+			.as_ref()
+			.to_vec(),
+		SignatureFormEnum::Recoverable => {
+			let (recovery_id, signature) = secp
+				.sign_recoverable(&message, &secret_key)
+				.serialize_compact();
+			let mut signature = signature.to_vec();
+			signature.push(recovery_id.to_i32() as u8);
+			signature
+		}
 	};
 
 	Ok(signature)
@@ -44,13 +61,16 @@ pub fn ec_sign_secp256k1(
 pub fn ec_verify_secp256k1(
 	public_key: Vec<u8>,
 	sig: Vec<u8>,
-	message: Vec<u8>,
+	digest: Vec<u8>,
 	sig_form: SignatureFormEnum,
 ) -> Result<(), String> {
-	let message = Message::from_hashed_data::<sha256::Hash>(&message);
+	let message = to_message(&digest)?;
 	let sig = match sig_form {
 		SignatureFormEnum::Fixed => Signature::from_compact(&sig),
 		SignatureFormEnum::Der => Signature::from_der(&sig),
+		SignatureFormEnum::Recoverable => {
+			to_recoverable_signature(&sig, None).map(|sig| sig.to_standard())
+		}
 	}
 	.map_err(|e| format!("Invalid signature: {}", e))?;
 
@@ -77,6 +97,56 @@ pub fn ec_pk_secp256k1(secret_key: Vec<u8>, compress: bool) -> Result<Vec<u8>, S
 	Ok(public_key)
 }
 
+pub fn ec_recover_secp256k1(
+	sig: Vec<u8>,
+	digest: Vec<u8>,
+	recovery_id: Option<u8>,
+	compress: bool,
+) -> Result<Vec<u8>, String> {
+	let message = to_message(&digest)?;
+	let sig = to_recoverable_signature(&sig, recovery_id)
+		.map_err(|e| format!("Invalid signature: {}", e))?;
+
+	let public_key = secp256k1::Secp256k1::verification_only()
+		.recover(&message, &sig)
+		.map_err(|e| format!("Failed to recover: {}", e))?;
+
+	let public_key = match compress {
+		true => public_key.serialize().to_vec(),
+		false => public_key.serialize_uncompressed().to_vec(),
+	};
+
+	Ok(public_key)
+}
+
+/// Take the leftmost 256 bits of the digest (SEC 1, 4.1.3)
+fn to_message(digest: &[u8]) -> Result<Message, String> {
+	let digest = digest
+		.get(..32)
+		.ok_or("Invalid digest length (should be at least 256 bits)")?;
+	Message::from_slice(digest).map_err(|e| format!("Invalid digest: {}", e))
+}
+
+/// Parse r || s || v, or r || s with a separate recovery id
+/// v may be 0 - 3, 27 - 30 (Ethereum) or 31 - 34 (Bitcoin, compressed)
+fn to_recoverable_signature(
+	sig: &[u8],
+	recovery_id: Option<u8>,
+) -> Result<RecoverableSignature, secp256k1::Error> {
+	let (sig, v) = match (sig.len(), recovery_id) {
+		(64, Some(v)) => (sig, v),
+		(65, None) => (&sig[..64], sig[64]),
+		_ => return Err(secp256k1::Error::InvalidSignature),
+	};
+	let v = match v {
+		27..=30 => v - 27,
+		31..=34 => v - 31,
+		_ => v,
+	};
+	let recovery_id = RecoveryId::from_i32(v as i32)?;
+	RecoverableSignature::from_compact(sig, recovery_id)
+}
+
 pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 	vec![
         ("ec_gk",
@@ -108,6 +178,38 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
                  is_test: true,
                  since: "0.7.0".to_string(),
              },
+             Case {
+                 desc: "Secp256k1 deterministic".to_string(),
+                 input: vec!["-c", "secp256k1", "-s", "0x9cb4f775e9b67118242cea15285555c287a7e3d2f86ba238c1fe87284b898e9a", "-d", "0x616263"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x7c77b65a27984b0e124a0ae2eec6bbf2b338a5c999b943abda576108f92e95364b0b983da055493c87fd138fe5673992b2a48ef85d9ad30c98fc1afcc5fc7bc0"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 recoverable signature form".to_string(),
+                 input: vec!["-c", "secp256k1", "-s", "0x9cb4f775e9b67118242cea15285555c287a7e3d2f86ba238c1fe87284b898e9a", "-f", "recoverable", "0x616263"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x7c77b65a27984b0e124a0ae2eec6bbf2b338a5c999b943abda576108f92e95364b0b983da055493c87fd138fe5673992b2a48ef85d9ad30c98fc1afcc5fc7bc000"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 Ethereum personal_sign".to_string(),
+                 input: vec!["-c", "secp256k1", "-s", "0x4c0883a69102937d9231471b5dbb6204fe5129617082792ae468d01a3f362318", "-P", "ethereum", "-f", "recoverable", "0x536f6d652064617461"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x488f69751b0daea7212cf31fad0bb8c39ec14901148896f272dfc7c84cc1051b3288318f78aacedb7ad52a8af1206810426a778c8d972f3bae5355d4e5c312b900"].into_iter().map(Into::into).collect(),
+                 is_example: true,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 Bitcoin signed message".to_string(),
+                 input: vec!["-c", "secp256k1", "-s", "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", "-P", "bitcoin", "-f", "recoverable", "0x5468697320697320616e206578616d706c65206f662061207369676e6564206d6573736167652e"].into_iter().map(Into::into).collect(),
+                 output: vec!["0xd2f9c8b163b62d104c784fc85ad9093d55fcc32706d5ca9a888a0d9efc343063111922e994d065d48c0ad920a0d9a9d7b072f48b49afca3b0a15f45f163dd67900"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
          ]),
         ("ec_verify",
          vec![
@@ -131,6 +233,14 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
                  is_test: true,
                  since: "0.7.0".to_string(),
              },
+             Case {
+                 desc: "Secp256k1 recoverable signature form".to_string(),
+                 input: vec!["-c", "secp256k1", "-p", "0x03391aa7238b79e1aad1e038c95306171a8ac7499357dc99586f96c5f3b9618d60", "-f", "recoverable", "-S", "0x7c77b65a27984b0e124a0ae2eec6bbf2b338a5c999b943abda576108f92e95364b0b983da055493c87fd138fe5673992b2a48ef85d9ad30c98fc1afcc5fc7bc000", "0x616263"].into_iter().map(Into::into).collect(),
+                 output: vec!["true"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
          ]),
         ("ec_pk",
          vec![
@@ -151,5 +261,40 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
                  since: "0.7.0".to_string(),
              },
          ]),
+        ("ec_recover",
+         vec![
+             Case {
+                 desc: "Secp256k1".to_string(),
+                 input: vec!["-c", "secp256k1", "-S", "0x7c77b65a27984b0e124a0ae2eec6bbf2b338a5c999b943abda576108f92e95364b0b983da055493c87fd138fe5673992b2a48ef85d9ad30c98fc1afcc5fc7bc000", "-C", "0x616263"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x03391aa7238b79e1aad1e038c95306171a8ac7499357dc99586f96c5f3b9618d60"].into_iter().map(Into::into).collect(),
+                 is_example: true,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 with recovery id".to_string(),
+                 input: vec!["-c", "secp256k1", "-S", "0x7c77b65a27984b0e124a0ae2eec6bbf2b338a5c999b943abda576108f92e95364b0b983da055493c87fd138fe5673992b2a48ef85d9ad30c98fc1afcc5fc7bc0", "-r", "27", "-C", "0x616263"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x03391aa7238b79e1aad1e038c95306171a8ac7499357dc99586f96c5f3b9618d60"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 Ethereum personal_sign".to_string(),
+                 input: vec!["-c", "secp256k1", "-S", "0x488f69751b0daea7212cf31fad0bb8c39ec14901148896f272dfc7c84cc1051b3288318f78aacedb7ad52a8af1206810426a778c8d972f3bae5355d4e5c312b900", "-P", "ethereum", "0x536f6d652064617461"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x0476c7536ef77ae8404a34d20e1ec08276212ff146dbf4fc52faaf413346934eaa379a2f5f71a11fa22d266b9a1d49e8b4733d52f6b42a0f731d50b349dc0feaa0"].into_iter().map(Into::into).collect(),
+                 is_example: true,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Secp256k1 Bitcoin signed message".to_string(),
+                 input: vec!["-c", "secp256k1", "-S", "0xd2f9c8b163b62d104c784fc85ad9093d55fcc32706d5ca9a888a0d9efc343063111922e994d065d48c0ad920a0d9a9d7b072f48b49afca3b0a15f45f163dd6791f", "-P", "bitcoin", "-C", "0x5468697320697320616e206578616d706c65206f662061207369676e6564206d6573736167652e"].into_iter().map(Into::into).collect(),
+                 output: vec!["0x03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd"].into_iter().map(Into::into).collect(),
+                 is_example: false,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+         ]),
     ].into_iter().collect()
 }
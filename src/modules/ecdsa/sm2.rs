@@ -26,9 +26,18 @@ pub fn ec_sign_sm2(
 	secret_key: Vec<u8>,
 	message: Vec<u8>,
 	sig_form: SignatureFormEnum,
+	deterministic: bool,
 ) -> Result<Vec<u8>, String> {
-	if let SignatureFormEnum::Der = sig_form {
-		return Err("DER form is not supported".to_string());
+	match sig_form {
+		SignatureFormEnum::Der => return Err("DER form is not supported".to_string()),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+		SignatureFormEnum::Fixed => (),
+	}
+
+	if deterministic {
+		return Err("Deterministic signing is not supported".to_string());
 	}
 
 	let secret_key = vec_to_secret_key(secret_key)?;
@@ -47,8 +56,12 @@ pub fn ec_verify_sm2(
 	message: Vec<u8>,
 	sig_form: SignatureFormEnum,
 ) -> Result<(), String> {
-	if let SignatureFormEnum::Der = sig_form {
-		return Err("DER form is not supported".to_string());
+	match sig_form {
+		SignatureFormEnum::Der => return Err("DER form is not supported".to_string()),
+		SignatureFormEnum::Recoverable => {
+			return Err("Recoverable form is not supported".to_string())
+		}
+		SignatureFormEnum::Fixed => (),
 	}
 
 	let public_key = vec_to_public_key(public_key)?;
@@ -194,6 +207,15 @@ fn slice_to_arr(slice: &[u8]) -> [u8; 8] {
 	a
 }
 
+pub fn ec_recover_sm2(
+	_sig: Vec<u8>,
+	_message: Vec<u8>,
+	_recovery_id: Option<u8>,
+	_compress: bool,
+) -> Result<Vec<u8>, String> {
+	Err("Recovery is not supported".to_string())
+}
+
 pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
 	vec![
 		("ec_gk",
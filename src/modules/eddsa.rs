@@ -1,5 +1,5 @@
 use crate::modules::base::Hex;
-use crate::modules::{base, Command, Module};
+use crate::modules::{base, key, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
 
 mod ed25519;
@@ -20,7 +20,17 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 	vec![
 		Command {
 			app: SubCommand::with_name("ed_gk")
-				.about("EdDSA generate key pair (Mini secret key, Public key)"),
+				.about("EdDSA generate key pair (Mini secret key, Public key)")
+				.arg(
+					Arg::with_name("FORMAT")
+						.long("format")
+						.short("f")
+						.help("Format\nraw: (Mini secret key, Public key)\njwk: Private JWK (RFC 7517)\n")
+						.takes_value(true)
+						.possible_values(&["raw", "jwk"])
+						.default_value("raw")
+						.required(false),
+				),
 			f: ed_gk,
 		},
 		Command {
@@ -39,7 +49,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Mini secret key (Mini private key, Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: ed_sign,
 		},
 		Command {
@@ -66,7 +78,8 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Signature (Hex)")
 						.takes_value(true)
 						.required(true),
-				),
+				)
+				.arg(base::in_format_arg()),
 			f: ed_verify,
 		},
 		Command {
@@ -79,15 +92,20 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Mini secret key (Mini private key, Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(base::out_format_arg()),
 			f: ed_pk,
 		},
 	]
 }
 
-fn ed_gk(_matches: &ArgMatches) -> Result<Vec<String>, String> {
+fn ed_gk(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let (private_key, public_key) = ed25519::ed_gk_ed25519()?;
 
+	if matches.value_of("FORMAT") == Some("jwk") {
+		return Ok(vec![key::to_jwk("ed25519", private_key)?]);
+	}
+
 	let (private_key, public_key): (String, String) =
 		(Hex::from(private_key).into(), Hex::from(public_key).into());
 
@@ -139,6 +157,15 @@ fn ed_pk(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	Ok(vec![result])
 }
 
+/// Sign the message with the mini secret key
+pub fn sign(secret_key: Vec<u8>, message: Vec<u8>) -> Result<Vec<u8>, String> {
+	ed25519::ed_sign_ed25519(AltSecretKey::MiniSecretKey(secret_key), message)
+}
+
+pub fn verify(public_key: Vec<u8>, sig: Vec<u8>, message: Vec<u8>) -> Result<(), String> {
+	ed25519::ed_verify_ed25519(public_key, sig, message)
+}
+
 fn get_alt_secret_key(matches: &ArgMatches) -> Result<AltSecretKey, String> {
 	if matches.is_present("MINI_SECRET_KEY") {
 		let secret_key = matches
@@ -1,17 +1,20 @@
 use crate::modules::base::Hex;
 use crate::modules::{base, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
-use crc::crc32;
+use crc::crc32::{self, Hasher32};
 use crypto::blake2b::Blake2b;
 use lazy_static::lazy_static;
+use regex::Regex;
 use ring::digest::{Context, SHA1_FOR_LEGACY_USE_ONLY};
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512, Sha512Trunc224, Sha512Trunc256};
 use std::collections::HashMap;
-use yogcrypt::sm3::sm3_enc;
+use std::fs::File;
+use std::io;
+use std::io::Read;
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
-		desc: "Hash (MD5, SHA-1, SHA-2, SHA-3, RIPEMD, CRC, Blake2b, SM3, Twox)".to_string(),
+		desc: "Hash / HMAC (MD5, SHA-1, SHA-2, SHA-3, RIPEMD, CRC, Blake2b, SM3, Twox)".to_string(),
 		commands: commands(),
 		get_cases: cases::cases,
 	}
@@ -21,137 +24,174 @@ struct Algorithm {
 	name: &'static str,
 	help: &'static str,
 	f: AlgorithmF,
+	/// Block size for HMAC
+	block_size: Option<usize>,
 }
 
 enum AlgorithmF {
-	Normal(fn(data: Vec<u8>) -> Result<Vec<u8>, String>),
-	WithKey(fn(data: Vec<u8>, key: Vec<u8>) -> Result<Vec<u8>, String>),
-	WithSeed(fn(data: Vec<u8>, seed: u64) -> Result<Vec<u8>, String>),
+	Normal(fn() -> Box<dyn Hasher>),
+	WithKey(fn(key: Vec<u8>) -> Box<dyn Hasher>),
+	WithSeed(fn(seed: u64) -> Box<dyn Hasher>),
 }
 
+/// Incremental hash interface
+trait Hasher {
+	fn update(&mut self, data: &[u8]);
+	fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
 lazy_static! {
 	static ref RAW_ALGORITHMS: Vec<Algorithm> = vec![
 		Algorithm {
 			name: "md5",
 			help: "MD5",
 			f: AlgorithmF::Normal(md5),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "sha1",
 			help: "SHA-1",
 			f: AlgorithmF::Normal(sha1),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "sha2_224",
 			help: "SHA-2 224",
 			f: AlgorithmF::Normal(sha2_224),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "sha2_256",
 			help: "SHA-2 256",
 			f: AlgorithmF::Normal(sha2_256),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "sha2_384",
 			help: "SHA-2 384",
 			f: AlgorithmF::Normal(sha2_384),
+			block_size: Some(128),
 		},
 		Algorithm {
 			name: "sha2_512",
 			help: "SHA-2 512",
 			f: AlgorithmF::Normal(sha2_512),
+			block_size: Some(128),
 		},
 		Algorithm {
 			name: "sha2_512_224",
 			help: "SHA-2 512 truncate 224",
 			f: AlgorithmF::Normal(sha2_512_224),
+			block_size: Some(128),
 		},
 		Algorithm {
 			name: "sha2_512_256",
 			help: "SHA-2 512 truncate 256",
 			f: AlgorithmF::Normal(sha2_512_256),
+			block_size: Some(128),
 		},
 		Algorithm {
 			name: "sha3_224",
 			help: "SHA-3 224",
 			f: AlgorithmF::Normal(sha3_224),
+			block_size: Some(144),
 		},
 		Algorithm {
 			name: "sha3_256",
 			help: "SHA-3 256",
 			f: AlgorithmF::Normal(sha3_256),
+			block_size: Some(136),
 		},
 		Algorithm {
 			name: "sha3_384",
 			help: "SHA-3 384",
 			f: AlgorithmF::Normal(sha3_384),
+			block_size: Some(104),
 		},
 		Algorithm {
 			name: "sha3_512",
 			help: "SHA-3 512",
 			f: AlgorithmF::Normal(sha3_512),
+			block_size: Some(72),
 		},
 		Algorithm {
 			name: "sha3_k_224",
 			help: "SHA-3 keccak 224",
 			f: AlgorithmF::Normal(sha3_k_224),
+			block_size: Some(144),
 		},
 		Algorithm {
 			name: "sha3_k_256",
 			help: "SHA-3 keccak 256",
 			f: AlgorithmF::Normal(sha3_k_256),
+			block_size: Some(136),
 		},
 		Algorithm {
 			name: "sha3_k_384",
 			help: "SHA-3 keccak 384",
 			f: AlgorithmF::Normal(sha3_k_384),
+			block_size: Some(104),
 		},
 		Algorithm {
 			name: "sha3_k_512",
 			help: "SHA-3 keccak 512",
 			f: AlgorithmF::Normal(sha3_k_512),
+			block_size: Some(72),
 		},
 		Algorithm {
 			name: "ripemd_160",
 			help: "RIPEMD-160",
 			f: AlgorithmF::Normal(ripemd_160),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "crc_32",
 			help: "CRC32",
 			f: AlgorithmF::Normal(crc_32),
+			block_size: None,
 		},
 		Algorithm {
 			name: "blake2b_160",
 			help: "Blake2b 160",
 			f: AlgorithmF::WithKey(blake2b_160),
+			block_size: None,
 		},
 		Algorithm {
 			name: "blake2b_256",
 			help: "Blake2b 256",
 			f: AlgorithmF::WithKey(blake2b_256),
+			block_size: None,
 		},
 		Algorithm {
 			name: "blake2b_384",
 			help: "Blake2b 384",
 			f: AlgorithmF::WithKey(blake2b_384),
+			block_size: None,
 		},
 		Algorithm {
 			name: "blake2b_512",
 			help: "Blake2b 512",
 			f: AlgorithmF::WithKey(blake2b_512),
+			block_size: None,
 		},
 		Algorithm {
 			name: "sm3",
 			help: "Chinese National Standard SM3",
 			f: AlgorithmF::Normal(sm3),
+			block_size: Some(64),
 		},
 		Algorithm {
 			name: "twox",
 			help: "TwoX",
 			f: AlgorithmF::WithSeed(twox),
+			block_size: None,
 		},
 	];
+	static ref GNU_SUMS_LINE: Regex = Regex::new(r"^([0-9a-fA-F]+) [ *](.+)$").expect("qed");
+	static ref BSD_SUMS_LINE: Regex =
+		Regex::new(r"^[\w-]+ ?\((.+)\) ?= ?([0-9a-fA-F]+)$").expect("qed");
 	static ref ALGORITHMS: HashMap<&'static str, &'static Algorithm> =
 		RAW_ALGORITHMS.iter().map(|x| (x.name, x)).collect();
 	static ref ALGORITHM_HELP: String = "Hash algorithm\n".to_string()
@@ -160,67 +200,248 @@ lazy_static! {
 			.map(|a| { format!("{}: {}", a.name, a.help) })
 			.collect::<Vec<String>>()
 			.join("\n");
+	static ref HMAC_ALGORITHM_NAMES: Vec<&'static str> = RAW_ALGORITHMS
+		.iter()
+		.filter(|a| a.block_size.is_some())
+		.map(|a| a.name)
+		.collect();
+	static ref HMAC_ALGORITHM_HELP: String = "Hash algorithm\n".to_string()
+		+ &RAW_ALGORITHMS
+			.iter()
+			.filter(|a| a.block_size.is_some())
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n");
 }
 
 pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
-	vec![Command {
-		app: SubCommand::with_name("hash")
-			.about("Hex to hash")
-			.arg(
-				Arg::with_name("ALGORITHM")
-					.long("algo")
-					.short("a")
-					.help(&ALGORITHM_HELP)
-					.takes_value(true)
-					.required(true),
-			)
-			.arg(
-				Arg::with_name("KEY")
-					.long("key")
-					.short("k")
-					.help("Key for Blake2b")
-					.takes_value(true)
-					.required(false),
-			)
-			.arg(
-				Arg::with_name("SEED")
-					.long("seed")
-					.short("s")
-					.help("Seed for twox")
-					.takes_value(true)
-					.required(false),
-			)
-			.arg(Arg::with_name("INPUT").required(false).index(1)),
-		f: hash,
-	}]
+	vec![
+		Command {
+			app: SubCommand::with_name("hash")
+				.about("Hex to hash")
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algo")
+						.short("a")
+						.help(&ALGORITHM_HELP)
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.long("key")
+						.short("k")
+						.help("Key for Blake2b")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SEED")
+						.long("seed")
+						.short("s")
+						.help("Seed for twox")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FILE")
+						.long("file")
+						.short("f")
+						.help("Files to hash in chunks (- for stdin)")
+						.takes_value(true)
+						.multiple(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("CHECK")
+						.long("check")
+						.short("c")
+						.help("Check digests listed in a sums file (sha256sum or BSD format, - for stdin)")
+						.takes_value(true)
+						.conflicts_with("FILE")
+						.required(false),
+				)
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg().conflicts_with_all(&["FILE", "CHECK"])),
+			f: hash,
+		},
+		Command {
+			app: SubCommand::with_name("hmac")
+				.about("Hex to HMAC")
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algo")
+						.short("a")
+						.help(&HMAC_ALGORITHM_HELP)
+						.takes_value(true)
+						.possible_values(&HMAC_ALGORITHM_NAMES)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.long("key")
+						.short("k")
+						.help("Key (Hex)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: hmac,
+		},
+	]
 }
 
 fn hash(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	if matches.is_present("CHECK") {
+		// ModuleManager::run prints the report of a failed check before the warning
+		return match check(matches)? {
+			(result, None) => Ok(result),
+			(_, Some(warning)) => Err(warning),
+		};
+	}
+
+	if let Some(files) = matches.values_of("FILE") {
+		// sha256sum style: <hex digest>  <file name>
+		return files
+			.map(|file| {
+				let result = hash_file(new_hasher(matches)?, file)?;
+				Ok(format!("{}  {}", hex::encode(result), file))
+			})
+			.collect();
+	}
+
 	let input = base::input_string(matches)?;
 
 	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
 
+	let mut hasher = new_hasher(matches)?;
+	hasher.update(&input);
+	let result = hasher.finalize();
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn new_hasher(matches: &ArgMatches) -> Result<Box<dyn Hasher>, String> {
+	let a_name = matches.value_of("ALGORITHM").ok_or("Invalid algorithm")?;
+
+	let algorithm = ALGORITHMS.get(a_name).ok_or("Invalid algorithm")?;
+
+	let hasher = match algorithm.f {
+		AlgorithmF::Normal(f) => (f)(),
+		AlgorithmF::WithKey(f) => {
+			let key = match matches.value_of("KEY") {
+				Some(key) => key.parse::<Hex>().map_err(|_| "Invalid key")?.into(),
+				None => vec![],
+			};
+			(f)(key)
+		}
+		AlgorithmF::WithSeed(f) => {
+			let seed = match matches.value_of("SEED") {
+				Some(seed) => seed.parse::<u64>().map_err(|_| "Invalid seed")?,
+				None => 0,
+			};
+			(f)(seed)
+		}
+	};
+	Ok(hasher)
+}
+
+/// Check the files listed in the sums file of hash --check
+/// Return the report lines, and a warning like sha256sum if any of them failed
+pub fn check(matches: &ArgMatches) -> Result<(Vec<String>, Option<String>), String> {
+	let sums_file = matches.value_of("CHECK").ok_or("Invalid sums file")?;
+
+	let (result, failed) = check_sums(|| new_hasher(matches), sums_file)?;
+
+	let warning = match failed {
+		0 => None,
+		_ => Some(format!(
+			"WARNING: {} of {} computed checksums did NOT match",
+			failed,
+			result.len()
+		)),
+	};
+
+	Ok((result, warning))
+}
+
+/// Check the files listed in a sums file, return the report lines and the number of failures
+fn check_sums<F>(new_hasher: F, sums_file: &str) -> Result<(Vec<String>, usize), String>
+where
+	F: Fn() -> Result<Box<dyn Hasher>, String>,
+{
+	let sums = match sums_file {
+		"-" => {
+			let mut sums = String::new();
+			base::stdin().read_to_string(&mut sums).map(|_| sums)
+		}
+		_ => std::fs::read_to_string(sums_file),
+	}
+	.map_err(|e| format!("{}: {}", sums_file, e))?;
+
+	let mut result = vec![];
+	let mut failed = 0;
+	for line in sums.lines() {
+		let (digest, file) = match parse_sums_line(line) {
+			Some(x) => x,
+			None => continue,
+		};
+		let status = match hash_file(new_hasher()?, &file) {
+			Ok(x) if hex::encode(&x) == digest.to_lowercase() => "OK",
+			Ok(_) => "FAILED",
+			Err(_) => "FAILED open or read",
+		};
+		if status != "OK" {
+			failed += 1;
+		}
+		result.push(format!("{}: {}", file, status));
+	}
+
+	if result.is_empty() {
+		return Err(format!(
+			"{}: no properly formatted checksum lines found",
+			sums_file
+		));
+	}
+
+	Ok((result, failed))
+}
+
+/// Parse a sums file line into (digest, file name)
+/// GNU: <digest>  <file> or <digest> *<file>
+/// BSD: <ALGORITHM> (<file>) = <digest>
+fn parse_sums_line(line: &str) -> Option<(String, String)> {
+	if let Some(caps) = BSD_SUMS_LINE.captures(line) {
+		return Some((caps[2].to_string(), caps[1].to_string()));
+	}
+	if let Some(caps) = GNU_SUMS_LINE.captures(line) {
+		return Some((caps[1].to_string(), caps[2].to_string()));
+	}
+	None
+}
+
+fn hmac(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let key = matches.value_of("KEY").ok_or("Invalid key")?;
+	let key: Vec<u8> = key.parse::<Hex>().map_err(|_| "Invalid key")?.into();
+
 	let a_name = matches.value_of("ALGORITHM").ok_or("Invalid algorithm")?;
 
 	let result = match ALGORITHMS.get(a_name) {
-		Some(a) => match a.f {
-			AlgorithmF::Normal(f) => (f)(input)?,
-			AlgorithmF::WithKey(f) => {
-				let key = match matches.value_of("KEY") {
-					Some(key) => key.parse::<Hex>().map_err(|_| "Invalid key")?.into(),
-					None => vec![],
-				};
-				(f)(input, key)?
-			}
-			AlgorithmF::WithSeed(f) => {
-				let seed = match matches.value_of("SEED") {
-					Some(seed) => seed.parse::<u64>().map_err(|_| "Invalid seed")?,
-					None => 0,
-				};
-				(f)(input, seed)?
-			}
-		},
-		None => return Err("Invalid algorithm".to_string()),
+		Some(Algorithm {
+			f: AlgorithmF::Normal(f),
+			block_size: Some(block_size),
+			..
+		}) => hmac_inner(*f, *block_size, &key, &input),
+		_ => return Err("Invalid algorithm".to_string()),
 	};
 
 	let result = Hex::from(result).into();
@@ -228,174 +449,264 @@ fn hash(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	Ok(vec![result])
 }
 
-fn md5(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	Ok(md5::compute(data).0.to_vec())
+/// HMAC (RFC 2104)
+fn hmac_inner(
+	new_hasher: fn() -> Box<dyn Hasher>,
+	block_size: usize,
+	key: &[u8],
+	data: &[u8],
+) -> Vec<u8> {
+	let mut key = if key.len() > block_size {
+		let mut hasher = new_hasher();
+		hasher.update(key);
+		hasher.finalize()
+	} else {
+		key.to_vec()
+	};
+	key.resize(block_size, 0);
+
+	let pad = |x: u8| key.iter().map(|k| k ^ x).collect::<Vec<u8>>();
+
+	let mut inner = new_hasher();
+	inner.update(&pad(0x36));
+	inner.update(data);
+	let inner = inner.finalize();
+
+	let mut outer = new_hasher();
+	outer.update(&pad(0x5c));
+	outer.update(&inner);
+	outer.finalize()
+}
+
+fn hash_file(hasher: Box<dyn Hasher>, file: &str) -> Result<Vec<u8>, String> {
+	let result = match file {
+		"-" => hash_reader(hasher, base::stdin()),
+		_ => File::open(file)
+			.map_err(|e| format!("{}", e))
+			.and_then(|reader| hash_reader(hasher, reader)),
+	};
+	result.map_err(|e| format!("{}: {}", file, e))
+}
+
+fn hash_reader<R: Read>(mut hasher: Box<dyn Hasher>, mut reader: R) -> Result<Vec<u8>, String> {
+	let mut buffer = vec![0u8; CHUNK_SIZE];
+	loop {
+		let len = match reader.read(&mut buffer) {
+			Ok(0) => break,
+			Ok(len) => len,
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+			Err(e) => return Err(format!("{}", e)),
+		};
+		hasher.update(&buffer[..len]);
+	}
+	Ok(hasher.finalize())
+}
+
+struct DigestHasher<D>(D);
+
+impl<D: Digest + 'static> DigestHasher<D> {
+	fn boxed(d: D) -> Box<dyn Hasher> {
+		Box::new(Self(d))
+	}
+}
+
+impl<D: Digest> Hasher for DigestHasher<D> {
+	fn update(&mut self, data: &[u8]) {
+		self.0.input(data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		self.0.result().to_vec()
+	}
+}
+
+impl Hasher for md5::Context {
+	fn update(&mut self, data: &[u8]) {
+		self.consume(data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		self.compute().0.to_vec()
+	}
+}
+
+impl Hasher for Context {
+	fn update(&mut self, data: &[u8]) {
+		Context::update(self, data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		self.finish().as_ref().to_vec()
+	}
+}
+
+impl Hasher for crc32::Digest {
+	fn update(&mut self, data: &[u8]) {
+		self.write(data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		self.sum32().to_be_bytes().to_vec()
+	}
 }
 
-fn sha1(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut context = Context::new(&SHA1_FOR_LEGACY_USE_ONLY);
-	context.update(&data);
-	let result = context.finish().as_ref().to_vec();
-	Ok(result)
+struct Blake2bHasher(Blake2b, usize);
+
+impl Hasher for Blake2bHasher {
+	fn update(&mut self, data: &[u8]) {
+		crypto::digest::Digest::input(&mut self.0, data);
+	}
+	fn finalize(mut self: Box<Self>) -> Vec<u8> {
+		let mut result = vec![0u8; self.1];
+		crypto::digest::Digest::result(&mut self.0, &mut result);
+		result
+	}
 }
 
-fn sha2_224(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha224::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+impl Hasher for sm3::Sm3 {
+	fn update(&mut self, data: &[u8]) {
+		sm3::Digest::update(self, data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		sm3::Digest::finalize(*self).to_vec()
+	}
 }
 
-fn sha2_256(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha256::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+impl Hasher for twox_hash::XxHash {
+	fn update(&mut self, data: &[u8]) {
+		::core::hash::Hasher::write(self, data);
+	}
+	fn finalize(self: Box<Self>) -> Vec<u8> {
+		use byteorder::{ByteOrder, LittleEndian};
+		let r = ::core::hash::Hasher::finish(&*self);
+		let mut dest = vec![0u8; 8];
+		LittleEndian::write_u64(&mut dest[0..8], r);
+		dest
+	}
 }
 
-fn sha2_384(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha384::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn md5() -> Box<dyn Hasher> {
+	Box::new(md5::Context::new())
 }
 
-fn sha2_512(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha512::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha1() -> Box<dyn Hasher> {
+	Box::new(Context::new(&SHA1_FOR_LEGACY_USE_ONLY))
 }
 
-fn sha2_512_224(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha512Trunc224::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_224() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha224::new())
 }
 
-fn sha2_512_256(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = Sha512Trunc256::new();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_256() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha256::new())
 }
 
-fn sha3_224(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Sha3_224::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_384() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha384::new())
 }
 
-fn sha3_256(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Sha3_256::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_512() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha512::new())
 }
 
-fn sha3_384(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Sha3_384::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_512_224() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha512Trunc224::new())
 }
 
-fn sha3_512(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Sha3_512::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha2_512_256() -> Box<dyn Hasher> {
+	DigestHasher::boxed(Sha512Trunc256::new())
 }
 
-fn sha3_k_224(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Keccak224::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha3_224() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Sha3_224::default())
 }
 
-fn sha3_k_256(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Keccak256::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha3_256() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Sha3_256::default())
 }
 
-fn sha3_k_384(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Keccak384::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha3_384() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Sha3_384::default())
 }
 
-fn sha3_k_512(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = sha3::Keccak512::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha3_512() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Sha3_512::default())
 }
 
-fn ripemd_160(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut hasher = ripemd160::Ripemd160::default();
-	hasher.input(data);
-	let result = hasher.result().to_vec();
-	Ok(result)
+fn sha3_k_224() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Keccak224::default())
 }
 
-fn crc_32(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let result = crc32::checksum_ieee(&data);
-	let result = result.to_be_bytes().to_vec();
+fn sha3_k_256() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Keccak256::default())
+}
 
-	Ok(result)
+fn sha3_k_384() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Keccak384::default())
 }
 
-fn blake2b_160(data: Vec<u8>, key: Vec<u8>) -> Result<Vec<u8>, String> {
-	blake2b(data, 20, key)
+fn sha3_k_512() -> Box<dyn Hasher> {
+	DigestHasher::boxed(sha3::Keccak512::default())
 }
 
-fn blake2b_256(data: Vec<u8>, key: Vec<u8>) -> Result<Vec<u8>, String> {
-	blake2b(data, 32, key)
+fn ripemd_160() -> Box<dyn Hasher> {
+	DigestHasher::boxed(ripemd160::Ripemd160::default())
 }
 
-fn blake2b_384(data: Vec<u8>, key: Vec<u8>) -> Result<Vec<u8>, String> {
-	blake2b(data, 48, key)
+fn crc_32() -> Box<dyn Hasher> {
+	Box::new(crc32::Digest::new(crc32::IEEE))
 }
 
-fn blake2b_512(data: Vec<u8>, key: Vec<u8>) -> Result<Vec<u8>, String> {
-	blake2b(data, 64, key)
+fn blake2b_160(key: Vec<u8>) -> Box<dyn Hasher> {
+	blake2b(20, key)
 }
 
-fn blake2b(data: Vec<u8>, size: usize, key: Vec<u8>) -> Result<Vec<u8>, String> {
-	let mut result = vec![0u8; size];
+fn blake2b_256(key: Vec<u8>) -> Box<dyn Hasher> {
+	blake2b(32, key)
+}
 
-	Blake2b::blake2b(&mut result, &data, &key);
+fn blake2b_384(key: Vec<u8>) -> Box<dyn Hasher> {
+	blake2b(48, key)
+}
 
-	Ok(result)
+fn blake2b_512(key: Vec<u8>) -> Box<dyn Hasher> {
+	blake2b(64, key)
 }
 
-fn sm3(data: Vec<u8>) -> Result<Vec<u8>, String> {
-	let result = sm3_enc(&data);
+fn blake2b(size: usize, key: Vec<u8>) -> Box<dyn Hasher> {
+	let hasher = if key.is_empty() {
+		Blake2b::new(size)
+	} else {
+		Blake2b::new_keyed(size, &key)
+	};
+	Box::new(Blake2bHasher(hasher, size))
+}
 
-	let result: Vec<u8> = result
-		.iter()
-		.map(|x| x.to_be_bytes().to_vec())
-		.flatten()
-		.collect();
+/// BLAKE2b of the data, output size in bytes
+pub fn blake2b_hash(size: usize, data: &[u8]) -> Vec<u8> {
+	digest(blake2b(size, vec![]), data)
+}
 
-	Ok(result)
+pub fn sha2_256_hash(data: &[u8]) -> Vec<u8> {
+	digest(sha2_256(), data)
 }
 
-fn twox(data: Vec<u8>, seed: u64) -> Result<Vec<u8>, String> {
-	use ::core::hash::Hasher;
-	let mut h = twox_hash::XxHash::with_seed(seed);
-	h.write(&data);
-	let r = h.finish();
-	use byteorder::{ByteOrder, LittleEndian};
-	let mut dest = vec![0u8; 8];
-	LittleEndian::write_u64(&mut dest[0..8], r);
-	Ok(dest)
+pub fn sha3_k_256_hash(data: &[u8]) -> Vec<u8> {
+	digest(sha3_k_256(), data)
+}
+
+/// RIPEMD-160 of SHA-256 (Bitcoin HASH160)
+pub fn hash160(data: &[u8]) -> Vec<u8> {
+	digest(ripemd_160(), &sha2_256_hash(data))
+}
+
+fn digest(mut hasher: Box<dyn Hasher>, data: &[u8]) -> Vec<u8> {
+	hasher.update(data);
+	hasher.finalize()
+}
+
+fn sm3() -> Box<dyn Hasher> {
+	Box::new(sm3::Sm3::default())
+}
+
+fn twox(seed: u64) -> Box<dyn Hasher> {
+	Box::new(twox_hash::XxHash::with_seed(seed))
 }
 
 mod cases {
@@ -630,6 +941,161 @@ mod cases {
 					 is_test: true,
 					 since: "0.10.0".to_string(),
 				 },
+				 Case {
+					 desc: "Files".to_string(),
+					 input: vec!["-a", "sha2_256", "-f", "a.txt", "b.txt"].into_iter().map(Into::into).collect(),
+					 output: vec!["ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a.txt", "cb8379ac2098aa165029e3938a51da0bcecfc008fd6795f401178647f96c5b34  b.txt"].into_iter().map(Into::into).collect(),
+					 is_example: true,
+					 is_test: false,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "Check".to_string(),
+					 input: vec!["-a", "sha2_256", "-c", "SHA256SUMS"].into_iter().map(Into::into).collect(),
+					 output: vec!["a.txt: OK", "b.txt: OK"].into_iter().map(Into::into).collect(),
+					 is_example: true,
+					 is_test: false,
+					 since: "0.13.0".to_string(),
+				 },
+			 ]),
+			("hmac",
+			 vec![
+				 Case {
+					 desc: "HMAC-MD5 (RFC 2202 test case 1)".to_string(),
+					 input: vec!["-a", "md5", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x9294727a3638bb1c13f48ef8158bfc9d"].into_iter().map(Into::into).collect(),
+					 is_example: true,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-MD5 (RFC 2202 test case 2)".to_string(),
+					 input: vec!["-a", "md5", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x750c783e6ab0b503eaa86e310a5db738"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-1 (RFC 2202 test case 1)".to_string(),
+					 input: vec!["-a", "sha1", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xb617318655057264e28bc0b6fb378c8ef146be00"].into_iter().map(Into::into).collect(),
+					 is_example: true,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-1 (RFC 2202 test case 2)".to_string(),
+					 input: vec!["-a", "sha1", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xeffcdf6ae5eb2fa2d27416d5f184df9c259a7c79"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 224 (RFC 4231 test case 1)".to_string(),
+					 input: vec!["-a", "sha2_224", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 224 (RFC 4231 test case 2)".to_string(),
+					 input: vec!["-a", "sha2_224", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xa30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 256 (RFC 4231 test case 1)".to_string(),
+					 input: vec!["-a", "sha2_256", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xb0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"].into_iter().map(Into::into).collect(),
+					 is_example: true,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 256 (RFC 4231 test case 2)".to_string(),
+					 input: vec!["-a", "sha2_256", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 256 (RFC 4231 test case 6)".to_string(),
+					 input: vec!["-a", "sha2_256", "-k", "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "0x54657374205573696e67204c6172676572205468616e20426c6f636b2d53697a65204b6579202d2048617368204b6579204669727374"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 384 (RFC 4231 test case 1)".to_string(),
+					 input: vec!["-a", "sha2_384", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xafd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 384 (RFC 4231 test case 2)".to_string(),
+					 input: vec!["-a", "sha2_384", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xaf45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 512 (RFC 4231 test case 1)".to_string(),
+					 input: vec!["-a", "sha2_512", "-k", "0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b", "0x4869205468657265"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-2 512 (RFC 4231 test case 2)".to_string(),
+					 input: vec!["-a", "sha2_512", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-3 256".to_string(),
+					 input: vec!["-a", "sha3_256", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xc7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SHA-3 keccak 256".to_string(),
+					 input: vec!["-a", "sha3_k_256", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xaa9aed448c7abc8b5e326ffa6a01cdedf7b4b831881468c044ba8dd4566369a1"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-RIPEMD-160".to_string(),
+					 input: vec!["-a", "ripemd_160", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0xdda6c0213a485a9e24f4742064a7f033b43c4069"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
+				 Case {
+					 desc: "HMAC-SM3".to_string(),
+					 input: vec!["-a", "sm3", "-k", "0x4a656665", "0x7768617420646f2079612077616e7420666f72206e6f7468696e673f"].into_iter().map(Into::into).collect(),
+					 output: vec!["0x2e87f1d16862e6d964b50a5200bf2b10b764faa9680a296a2405f24bec39f882"].into_iter().map(Into::into).collect(),
+					 is_example: false,
+					 is_test: true,
+					 since: "0.13.0".to_string(),
+				 },
 			 ]),
 		].into_iter().collect()
 	}
@@ -644,4 +1110,91 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_hash_reader() {
+		let data = vec![0x61u8; CHUNK_SIZE * 2 + 1];
+		for algorithm in RAW_ALGORITHMS.iter() {
+			let new_hasher = || match algorithm.f {
+				AlgorithmF::Normal(f) => f(),
+				AlgorithmF::WithKey(f) => f(vec![0x01]),
+				AlgorithmF::WithSeed(f) => f(1),
+			};
+			let mut hasher = new_hasher();
+			hasher.update(&data);
+			let expected = hasher.finalize();
+			let result = hash_reader(new_hasher(), &data[..]);
+			assert_eq!(result, Ok(expected), "Test: {}", algorithm.name);
+		}
+	}
+
+	#[test]
+	fn test_hash_file() {
+		let file = std::env::temp_dir().join("dtool_test_hash_file");
+		std::fs::write(&file, b"abc").unwrap();
+		let file = file.to_str().unwrap();
+
+		let app = &commands()[0].app;
+		let matches = app
+			.clone()
+			.get_matches_from(vec!["hash", "-a", "sha2_256", "-f", file, file]);
+		let line = format!(
+			"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  {}",
+			file
+		);
+		assert_eq!(hash(&matches), Ok(vec![line.clone(), line]));
+	}
+
+	#[test]
+	fn test_check() {
+		let dir = std::env::temp_dir();
+		let file = dir.join("dtool_test_check");
+		std::fs::write(&file, b"abc").unwrap();
+		let file = file.to_str().unwrap();
+
+		let sums_file = dir.join("dtool_test_check_sums");
+		let sums_file = sums_file.to_str().unwrap();
+		let app = &commands()[0].app;
+		let check = |sums: String| {
+			std::fs::write(sums_file, sums).unwrap();
+			let matches = app
+				.clone()
+				.get_matches_from(vec!["hash", "-a", "sha2_256", "-c", sums_file]);
+			hash(&matches)
+		};
+
+		let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+		assert_eq!(
+			check(format!(
+				"{}  {}\nSHA256 ({}) = {}\n",
+				digest, file, file, digest
+			)),
+			Ok(vec![format!("{}: OK", file), format!("{}: OK", file)])
+		);
+		assert_eq!(
+			check(format!(
+				"{} *{}\n{}  {}.missing\n",
+				&digest[1..],
+				file,
+				digest,
+				file
+			)),
+			Err("WARNING: 2 of 2 computed checksums did NOT match".to_string())
+		);
+		// the report of a failed check is still returned
+		let matches = app
+			.clone()
+			.get_matches_from(vec!["hash", "-a", "sha2_256", "-c", sums_file]);
+		assert_eq!(
+			super::check(&matches),
+			Ok((
+				vec![
+					format!("{}: FAILED", file),
+					format!("{}.missing: FAILED open or read", file)
+				],
+				Some("WARNING: 2 of 2 computed checksums did NOT match".to_string())
+			))
+		);
+		assert!(check("invalid".to_string()).is_err());
+	}
 }
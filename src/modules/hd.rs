@@ -0,0 +1,654 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, hash, Command, Module};
+use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use p256::elliptic_curve::group::{Curve as _, Group};
+use p256::elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use p256::elliptic_curve::{
+	AffinePoint, CurveArithmetic, FieldBytesSize, NonZeroScalar, PublicKey, SecretKey,
+};
+use ring::hmac;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::collections::HashMap;
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "HD key derivation (BIP-32, SLIP-10)".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+struct Curve {
+	name: &'static str,
+	help: &'static str,
+	/// HMAC key of the master key generation (SLIP-10)
+	seed_key: &'static [u8],
+	derive_secret_f: fn(&ExtendedKey, u32) -> Result<ExtendedKey, String>,
+	derive_public_f: fn(&ExtendedKey, u32) -> Result<ExtendedKey, String>,
+	public_key_f: fn(&[u8]) -> Result<Vec<u8>, String>,
+	/// BIP-32 serialization (xprv / xpub)
+	serializable: bool,
+}
+
+lazy_static! {
+	static ref RAW_CURVES: Vec<Curve> = vec![
+		Curve {
+			name: "secp256k1",
+			help: "Secp256k1 (BIP-32)",
+			seed_key: b"Bitcoin seed",
+			derive_secret_f: ec_derive_secret::<k256::Secp256k1>,
+			derive_public_f: ec_derive_public::<k256::Secp256k1>,
+			public_key_f: ec_public_key::<k256::Secp256k1>,
+			serializable: true,
+		},
+		Curve {
+			name: "p256",
+			help: "NIST P-256 (SLIP-10)",
+			seed_key: b"Nist256p1 seed",
+			derive_secret_f: ec_derive_secret::<p256::NistP256>,
+			derive_public_f: ec_derive_public::<p256::NistP256>,
+			public_key_f: ec_public_key::<p256::NistP256>,
+			serializable: false,
+		},
+		Curve {
+			name: "ed25519",
+			help: "Ed25519 (SLIP-10, hardened only)",
+			seed_key: b"ed25519 seed",
+			derive_secret_f: ed25519_derive_secret,
+			derive_public_f: ed25519_derive_public,
+			public_key_f: ed25519_public_key,
+			serializable: false,
+		},
+	];
+	static ref CURVES: HashMap<&'static str, &'static Curve> =
+		RAW_CURVES.iter().map(|x| (x.name, x)).collect();
+	static ref CURVE_NAMES: Vec<&'static str> = RAW_CURVES.iter().map(|x| x.name).collect();
+	static ref CURVE_HELP: String = "Curve\n".to_string()
+		+ &RAW_CURVES
+			.iter()
+			.map(|a| { format!("{}: {}", a.name, a.help) })
+			.collect::<Vec<String>>()
+			.join("\n")
+		+ "\n";
+}
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// Version bytes of the BIP-32 serialization: (network, private, public)
+const VERSIONS: [(&str, [u8; 4], [u8; 4]); 2] = [
+	(
+		"mainnet",
+		[0x04, 0x88, 0xad, 0xe4],
+		[0x04, 0x88, 0xb2, 0x1e],
+	),
+	(
+		"testnet",
+		[0x04, 0x35, 0x83, 0x94],
+		[0x04, 0x35, 0x87, 0xcf],
+	),
+];
+
+const EXTENDED_KEY_PREFIXES: [&str; 4] = ["xprv", "xpub", "tprv", "tpub"];
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![Command {
+		app: SubCommand::with_name("hd_derive")
+			.about("HD derive key (Secret key, Public key)")
+			.arg(
+				Arg::with_name("INPUT")
+					.help("Seed (Hex) or extended key (xprv / xpub / tprv / tpub)")
+					.required(false)
+					.index(1),
+			)
+			.arg(
+				Arg::with_name("CURVE")
+					.long("curve")
+					.short("c")
+					.help(&CURVE_HELP)
+					.takes_value(true)
+					.possible_values(&CURVE_NAMES)
+					.default_value("secp256k1")
+					.required(false),
+			)
+			.arg(
+				Arg::with_name("PATH")
+					.long("path")
+					.short("p")
+					.help("Derivation path, e.g. m/44'/60'/0'/0/0 or m/44h/60h/0h/0/0")
+					.takes_value(true)
+					.default_value("m")
+					.required(false),
+			)
+			.arg(
+				Arg::with_name("FORMAT")
+					.long("format")
+					.short("f")
+					.help("Format\nraw: (Secret key, Public key), or Public key from xpub\nextended: (xprv, xpub), or xpub from xpub (secp256k1)\n")
+					.takes_value(true)
+					.possible_values(&["raw", "extended"])
+					.default_value("raw")
+					.required(false),
+			)
+			.arg(
+				Arg::with_name("NETWORK")
+					.long("network")
+					.short("n")
+					.help("Network of the extended key from seed\nmainnet: xprv / xpub\ntestnet: tprv / tpub\n")
+					.takes_value(true)
+					.possible_values(&["mainnet", "testnet"])
+					.default_value("mainnet")
+					.required(false),
+			),
+		f: hd_derive,
+	}]
+}
+
+#[derive(Clone)]
+enum Key {
+	Secret(Vec<u8>),
+	Public(Vec<u8>),
+}
+
+#[derive(Clone)]
+struct ExtendedKey {
+	depth: u8,
+	parent_fingerprint: [u8; 4],
+	child_number: u32,
+	chain_code: Vec<u8>,
+	key: Key,
+	network: &'static str,
+}
+
+fn hd_derive(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input = input.trim();
+
+	let curve = matches.value_of("CURVE").ok_or("Invalid curve")?;
+	let curve = CURVES.get(curve).ok_or("Invalid curve")?;
+
+	let path = matches.value_of("PATH").ok_or("Invalid path")?;
+	let path = parse_path(path)?;
+
+	let network = matches.value_of("NETWORK").ok_or("Invalid network")?;
+	let (network, _, _) = VERSIONS
+		.iter()
+		.find(|x| x.0 == network)
+		.ok_or("Invalid network")?;
+
+	let key = match EXTENDED_KEY_PREFIXES.iter().any(|x| input.starts_with(x)) {
+		true => {
+			if !curve.serializable {
+				return Err("Extended key is only supported with secp256k1".to_string());
+			}
+			deserialize(input)?
+		}
+		false => {
+			let seed: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid seed")?.into();
+			master_key(curve, &seed, network)?
+		}
+	};
+
+	let key = path.into_iter().try_fold(key, |key, index| match key.key {
+		Key::Secret(_) => (curve.derive_secret_f)(&key, index),
+		Key::Public(_) => (curve.derive_public_f)(&key, index),
+	})?;
+
+	let result = match (matches.value_of("FORMAT"), &key.key) {
+		(Some("extended"), _) if !curve.serializable => {
+			return Err("Extended format is only supported with secp256k1".to_string())
+		}
+		(Some("extended"), Key::Secret(secret_key)) => {
+			let public_key = (curve.public_key_f)(secret_key)?;
+			let public = ExtendedKey {
+				key: Key::Public(public_key),
+				..key.clone()
+			};
+			format!("({}, {})", serialize(&key)?, serialize(&public)?)
+		}
+		(Some("extended"), Key::Public(_)) => serialize(&key)?,
+		(_, Key::Secret(secret_key)) => {
+			let public_key = (curve.public_key_f)(secret_key)?;
+			let (secret_key, public_key): (String, String) = (
+				Hex::from(secret_key.clone()).into(),
+				Hex::from(public_key).into(),
+			);
+			format!("({}, {})", secret_key, public_key)
+		}
+		(_, Key::Public(public_key)) => Hex::from(public_key.clone()).into(),
+	};
+
+	Ok(vec![result])
+}
+
+/// Parse the path like m/44'/60'/0'/0/0 (h / H is also accepted for hardened)
+fn parse_path(path: &str) -> Result<Vec<u32>, String> {
+	let mut items = path.trim().split('/');
+	if items.next() != Some("m") {
+		return Err("Invalid path: should start with m".to_string());
+	}
+	items
+		.map(|item| {
+			let (index, hardened) = match item.strip_suffix(&['\'', 'h', 'H'][..]) {
+				Some(index) => (index, true),
+				None => (item, false),
+			};
+			let index = index
+				.parse::<u32>()
+				.ok()
+				.filter(|x| *x < HARDENED)
+				.ok_or_else(|| format!("Invalid path: {}", item))?;
+			Ok(match hardened {
+				true => index | HARDENED,
+				false => index,
+			})
+		})
+		.collect()
+}
+
+fn master_key(curve: &Curve, seed: &[u8], network: &'static str) -> Result<ExtendedKey, String> {
+	if seed.len() < 16 || seed.len() > 64 {
+		return Err("Invalid seed: 16 to 64 bytes expected".to_string());
+	}
+	let mut i = hmac_sha512(curve.seed_key, seed);
+	// SLIP-10: retry with I as the data if IL is not a valid secret key
+	while (curve.public_key_f)(&i[..32]).is_err() {
+		i = hmac_sha512(curve.seed_key, &i);
+	}
+	Ok(ExtendedKey {
+		depth: 0,
+		parent_fingerprint: [0; 4],
+		child_number: 0,
+		chain_code: i[32..].to_vec(),
+		key: Key::Secret(i[..32].to_vec()),
+		network,
+	})
+}
+
+fn ec_derive_secret<C>(parent: &ExtendedKey, index: u32) -> Result<ExtendedKey, String>
+where
+	C: CurveArithmetic,
+	AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+	FieldBytesSize<C>: ModulusSize,
+{
+	let secret_key = match &parent.key {
+		Key::Secret(secret_key) => secret_key,
+		Key::Public(_) => return Err("Invalid secret key".to_string()),
+	};
+	let parent_secret_key =
+		SecretKey::<C>::from_slice(secret_key).map_err(|_| "Invalid secret key")?;
+	let parent_public_key = ec_public_key::<C>(secret_key)?;
+
+	let data = match index >= HARDENED {
+		true => [&[0x00][..], secret_key].concat(),
+		false => parent_public_key.clone(),
+	};
+	let (secret_key, chain_code) = ec_child(&parent.chain_code, data, index, |il| {
+		let il = SecretKey::<C>::from_slice(il).ok()?;
+		let scalar = *il.to_nonzero_scalar() + *parent_secret_key.to_nonzero_scalar();
+		let secret_key: Option<NonZeroScalar<C>> = NonZeroScalar::new(scalar).into();
+		secret_key.map(|x| SecretKey::<C>::from(&x).to_bytes().to_vec())
+	});
+
+	child_key(
+		parent,
+		&parent_public_key,
+		index,
+		chain_code,
+		Key::Secret(secret_key),
+	)
+}
+
+fn ec_derive_public<C>(parent: &ExtendedKey, index: u32) -> Result<ExtendedKey, String>
+where
+	C: CurveArithmetic,
+	AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+	FieldBytesSize<C>: ModulusSize,
+{
+	let public_key = match &parent.key {
+		Key::Public(public_key) => public_key,
+		Key::Secret(_) => return Err("Invalid public key".to_string()),
+	};
+	if index >= HARDENED {
+		return Err("Hardened derivation requires a secret key".to_string());
+	}
+	let parent_public_key =
+		PublicKey::<C>::from_sec1_bytes(public_key).map_err(|_| "Invalid public key")?;
+
+	let (public_key, chain_code) = ec_child(&parent.chain_code, public_key.clone(), index, |il| {
+		let il = SecretKey::<C>::from_slice(il).ok()?;
+		let point = C::ProjectivePoint::generator() * *il.to_nonzero_scalar()
+			+ parent_public_key.to_projective();
+		let public_key = PublicKey::<C>::from_affine(point.to_affine()).ok()?;
+		Some(public_key.to_encoded_point(true).as_bytes().to_vec())
+	});
+
+	child_key(
+		parent,
+		&parent.key_bytes(),
+		index,
+		chain_code,
+		Key::Public(public_key),
+	)
+}
+
+/// CKD with the SLIP-10 retry: I = HMAC-SHA512(c, 0x01 || IR || index) while the child key is invalid
+fn ec_child<F>(chain_code: &[u8], data: Vec<u8>, index: u32, child: F) -> (Vec<u8>, Vec<u8>)
+where
+	F: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+	let mut data = data;
+	loop {
+		let i = hmac_sha512(chain_code, &[&data[..], &index.to_be_bytes()].concat());
+		match child(&i[..32]) {
+			Some(key) => return (key, i[32..].to_vec()),
+			None => data = [&[0x01][..], &i[32..]].concat(),
+		}
+	}
+}
+
+fn ec_public_key<C>(secret_key: &[u8]) -> Result<Vec<u8>, String>
+where
+	C: CurveArithmetic,
+	AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+	FieldBytesSize<C>: ModulusSize,
+{
+	let secret_key = SecretKey::<C>::from_slice(secret_key).map_err(|_| "Invalid secret key")?;
+	Ok(secret_key
+		.public_key()
+		.to_encoded_point(true)
+		.as_bytes()
+		.to_vec())
+}
+
+fn ed25519_derive_secret(parent: &ExtendedKey, index: u32) -> Result<ExtendedKey, String> {
+	let secret_key = match &parent.key {
+		Key::Secret(secret_key) => secret_key,
+		Key::Public(_) => return Err("Invalid secret key".to_string()),
+	};
+	if index < HARDENED {
+		return Err("Ed25519 only supports hardened derivation".to_string());
+	}
+	let data = [&[0x00][..], secret_key, &index.to_be_bytes()].concat();
+	let i = hmac_sha512(&parent.chain_code, &data);
+
+	let parent_public_key = [&[0x00][..], &ed25519_public_key(secret_key)?].concat();
+	child_key(
+		parent,
+		&parent_public_key,
+		index,
+		i[32..].to_vec(),
+		Key::Secret(i[..32].to_vec()),
+	)
+}
+
+fn ed25519_derive_public(_parent: &ExtendedKey, _index: u32) -> Result<ExtendedKey, String> {
+	Err("Ed25519 only supports hardened derivation".to_string())
+}
+
+fn ed25519_public_key(secret_key: &[u8]) -> Result<Vec<u8>, String> {
+	let key_pair =
+		Ed25519KeyPair::from_seed_unchecked(secret_key).map_err(|_| "Invalid secret key")?;
+	Ok(key_pair.public_key().as_ref().to_vec())
+}
+
+fn child_key(
+	parent: &ExtendedKey,
+	parent_public_key: &[u8],
+	index: u32,
+	chain_code: Vec<u8>,
+	key: Key,
+) -> Result<ExtendedKey, String> {
+	// the depth is serialized as a single byte
+	let depth = parent.depth.checked_add(1).ok_or("Invalid path: depth")?;
+	let mut parent_fingerprint = [0u8; 4];
+	parent_fingerprint.copy_from_slice(&hash::hash160(parent_public_key)[..4]);
+	Ok(ExtendedKey {
+		depth,
+		parent_fingerprint,
+		child_number: index,
+		chain_code,
+		key,
+		network: parent.network,
+	})
+}
+
+impl ExtendedKey {
+	fn key_bytes(&self) -> Vec<u8> {
+		match &self.key {
+			Key::Secret(secret_key) => [&[0x00][..], secret_key].concat(),
+			Key::Public(public_key) => public_key.clone(),
+		}
+	}
+}
+
+fn serialize(key: &ExtendedKey) -> Result<String, String> {
+	let (_, private, public) = VERSIONS
+		.iter()
+		.find(|x| x.0 == key.network)
+		.ok_or("Invalid network")?;
+	let version = match key.key {
+		Key::Secret(_) => private,
+		Key::Public(_) => public,
+	};
+	let data = [
+		&version[..],
+		&[key.depth],
+		&key.parent_fingerprint,
+		&key.child_number.to_be_bytes(),
+		&key.chain_code,
+		&key.key_bytes(),
+	]
+	.concat();
+	Ok(bs58::encode(data).with_check().into_string())
+}
+
+fn deserialize(input: &str) -> Result<ExtendedKey, String> {
+	let data = bs58::decode(input)
+		.with_check(None)
+		.into_vec()
+		.map_err(|_| "Invalid extended key")?;
+	if data.len() != 78 {
+		return Err("Invalid extended key: length".to_string());
+	}
+	let (network, key) = VERSIONS
+		.iter()
+		.find_map(|(network, private, public)| match &data[..4] {
+			x if x == private && data[45] == 0x00 => {
+				Some((*network, Key::Secret(data[46..].to_vec())))
+			}
+			x if x == public => Some((*network, Key::Public(data[45..].to_vec()))),
+			_ => None,
+		})
+		.ok_or("Invalid extended key: version")?;
+	match &key {
+		Key::Secret(secret_key) => ec_public_key::<k256::Secp256k1>(secret_key).map(|_| ()),
+		Key::Public(public_key) => k256::PublicKey::from_sec1_bytes(public_key)
+			.map(|_| ())
+			.map_err(|_| "Invalid public key".to_string()),
+	}?;
+	let mut parent_fingerprint = [0u8; 4];
+	parent_fingerprint.copy_from_slice(&data[5..9]);
+	let mut child_number = [0u8; 4];
+	child_number.copy_from_slice(&data[9..13]);
+	Ok(ExtendedKey {
+		depth: data[4],
+		parent_fingerprint,
+		child_number: u32::from_be_bytes(child_number),
+		chain_code: data[13..45].to_vec(),
+		key,
+		network,
+	})
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Vec<u8> {
+	let key = hmac::Key::new(hmac::HMAC_SHA512, key);
+	hmac::sign(&key, data).as_ref().to_vec()
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"hd_derive",
+				vec![
+					Case {
+						desc: "secp256k1".to_string(),
+						input: vec![
+							"-p",
+							"m/44h/60h/0h/0/0",
+							"0x000102030405060708090a0b0c0d0e0f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(0xe22f5526ce620ec69441c3453d7a0acbc26c3fc7543023f338123fd45c7d44b3, 0x03844a5d329470697de9926c9c98839ea33b6dd9507a896194ae2b91d71faa16d6)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "secp256k1, extended key (BIP-32 test vector 1)".to_string(),
+						input: vec![
+							"-p",
+							"m/0h/1/2h",
+							"-f",
+							"extended",
+							"0x000102030405060708090a0b0c0d0e0f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM, xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Public derivation from xpub".to_string(),
+						input: vec![
+							"-p",
+							"m/1/2",
+							"xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x026a5857b29f2b0529c907a3ad9dc9c964df0be4682432af3ba8747800dd13a902",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "p256 (SLIP-10)".to_string(),
+						input: vec![
+							"-c",
+							"p256",
+							"-p",
+							"m/0h/1/2h",
+							"0x000102030405060708090a0b0c0d0e0f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(0x694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7, 0x0359cf160040778a4b14c5f4d7b76e327ccc8c4a6086dd9451b7482b5a4972dda0)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ed25519 (SLIP-10)".to_string(),
+						input: vec![
+							"-c",
+							"ed25519",
+							"-p",
+							"m/0h/1h",
+							"0x000102030405060708090a0b0c0d0e0f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(0xb1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2, 0x1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Testnet".to_string(),
+						input: vec![
+							"-n",
+							"testnet",
+							"-f",
+							"extended",
+							"0x000102030405060708090a0b0c0d0e0f",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"(tprv8ZgxMBicQKsPeDgjzdC36fs6bMjGApWDNLR9erAXMs5skhMv36j9MV5ecvfavji5khqjWaWSFhN3YcCUUdiKH6isR4Pwy3U5y5egddBr16m, tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp)",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+
+	#[test]
+	fn test_depth_overflow() {
+		let curve = CURVES.get("secp256k1").unwrap();
+		let mut key = master_key(curve, &[0u8; 16], "mainnet").unwrap();
+		key.depth = u8::MAX;
+		assert_eq!(
+			(curve.derive_secret_f)(&key, 0).err(),
+			Some("Invalid path: depth".to_string())
+		);
+	}
+}
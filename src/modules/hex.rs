@@ -18,37 +18,43 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 		Command {
 			app: SubCommand::with_name("h2s")
 				.about("Convert hex to UTF-8 string")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
 			f: h2s,
 		},
 		Command {
 			app: SubCommand::with_name("s2h")
 				.about("Convert UTF-8 string to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
 			f: s2h,
 		},
 		Command {
 			app: SubCommand::with_name("h2b")
 				.about("Convert hex to binary")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
 			f: h2b,
 		},
 		Command {
 			app: SubCommand::with_name("b2h")
 				.about("Convert binary to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
 			f: b2h,
 		},
 		Command {
 			app: SubCommand::with_name("h2a")
 				.about("Convert hex to byte array")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::in_format_arg()),
 			f: h2a,
 		},
 		Command {
 			app: SubCommand::with_name("a2h")
 				.about("Convert byte array to hex")
-				.arg(Arg::with_name("INPUT").required(false).index(1)),
+				.arg(Arg::with_name("INPUT").required(false).index(1))
+				.arg(base::out_format_arg()),
 			f: a2h,
 		},
 	]
@@ -0,0 +1,833 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, Command, Module};
+use argon2::{Argon2, AssociatedData, ParamsBuilder};
+use clap::{Arg, ArgMatches, SubCommand};
+use crypto::digest::Digest;
+use crypto::hkdf::{hkdf_expand, hkdf_extract};
+use crypto::sha1::Sha1;
+use crypto::sha2::{Sha256, Sha384, Sha512};
+use rand::{thread_rng, RngCore};
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Key derivation (HKDF, scrypt, Argon2, bcrypt)".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("hkdf")
+				.about("HKDF (RFC 5869)")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Input key material (Hex), or PRK for expand")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algorithm")
+						.short("a")
+						.help("Algorithm\nsha1: SHA-1\nsha2_256: SHA-2 256\nsha2_384: SHA-2 384\nsha2_512: SHA-2 512\n")
+						.takes_value(true)
+						.possible_values(&["sha1", "sha2_256", "sha2_384", "sha2_512"])
+						.default_value("sha2_256")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MODE")
+						.long("mode")
+						.short("m")
+						.help("Mode\nfull: Extract then expand\nextract: Extract only (output PRK)\nexpand: Expand only (input PRK)\n")
+						.takes_value(true)
+						.possible_values(&["full", "extract", "expand"])
+						.default_value("full")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SALT")
+						.long("salt")
+						.short("s")
+						.help("Salt (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INFO")
+						.long("info")
+						.short("i")
+						.help("Info (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY_LENGTH")
+						.long("key-length")
+						.short("l")
+						.help("Key length: must be a multiple of 8")
+						.takes_value(true)
+						.default_value("256")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: hkdf,
+		},
+		Command {
+			app: SubCommand::with_name("scrypt")
+				.about("scrypt (RFC 7914)")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("SALT")
+						.long("salt")
+						.short("s")
+						.help("Salt (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("COST")
+						.long("cost")
+						.short("n")
+						.help("CPU/memory cost N: must be a power of 2")
+						.takes_value(true)
+						.default_value("16384")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("BLOCK_SIZE")
+						.long("block-size")
+						.short("r")
+						.help("Block size r")
+						.takes_value(true)
+						.default_value("8")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PARALLELISM")
+						.long("parallelism")
+						.short("p")
+						.help("Parallelism p")
+						.takes_value(true)
+						.default_value("1")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY_LENGTH")
+						.long("key-length")
+						.short("l")
+						.help("Key length: must be a multiple of 8")
+						.takes_value(true)
+						.default_value("256")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: scrypt,
+		},
+		Command {
+			app: SubCommand::with_name("argon2")
+				.about("Argon2 (RFC 9106)")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algorithm")
+						.short("a")
+						.help("Algorithm\nargon2id: Argon2id\nargon2i: Argon2i\nargon2d: Argon2d\n")
+						.takes_value(true)
+						.possible_values(&["argon2id", "argon2i", "argon2d"])
+						.default_value("argon2id")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SALT")
+						.long("salt")
+						.short("s")
+						.help("Salt (Hex, at least 8 bytes)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("MEMORY")
+						.long("memory")
+						.short("m")
+						.help("Memory size (KiB)")
+						.takes_value(true)
+						.default_value("19456")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ITERATIONS")
+						.long("iterations")
+						.short("t")
+						.help("Iterations")
+						.takes_value(true)
+						.default_value("2")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PARALLELISM")
+						.long("parallelism")
+						.short("p")
+						.help("Parallelism")
+						.takes_value(true)
+						.default_value("1")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SECRET")
+						.long("secret")
+						.short("k")
+						.help("Secret (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("AD")
+						.long("ad")
+						.short("d")
+						.help("Associated data (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY_LENGTH")
+						.long("key-length")
+						.short("l")
+						.help("Key length: must be a multiple of 8")
+						.takes_value(true)
+						.default_value("256")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
+			f: argon2,
+		},
+		Command {
+			app: SubCommand::with_name("bcrypt")
+				.about("bcrypt hash")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("COST")
+						.long("cost")
+						.short("c")
+						.help("Cost")
+						.takes_value(true)
+						.default_value("12")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SALT")
+						.long("salt")
+						.short("s")
+						.help("Salt (Hex, 16 bytes, random if not given)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: bcrypt,
+		},
+		Command {
+			app: SubCommand::with_name("bcrypt_verify")
+				.about("bcrypt verify")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.short("H")
+						.help("Hash (e.g. $2b$12$...)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(base::in_format_arg()),
+			f: bcrypt_verify,
+		},
+	]
+}
+
+fn hkdf(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = get_input(matches)?;
+	let salt = get_hex_arg(matches, "SALT", "Invalid salt")?;
+	let info = get_hex_arg(matches, "INFO", "Invalid info")?;
+	let key_length = get_key_length(matches)?;
+	let mode = matches.value_of("MODE").ok_or("Invalid mode")?;
+
+	let result = match matches.value_of("ALGORITHM") {
+		Some("sha1") => hkdf_inner(Sha1::new(), mode, &input, &salt, &info, key_length),
+		Some("sha2_256") => hkdf_inner(Sha256::new(), mode, &input, &salt, &info, key_length),
+		Some("sha2_384") => hkdf_inner(Sha384::new(), mode, &input, &salt, &info, key_length),
+		Some("sha2_512") => hkdf_inner(Sha512::new(), mode, &input, &salt, &info, key_length),
+		_ => Err("Invalid algorithm".to_string()),
+	}?;
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn hkdf_inner<D: Digest + Clone>(
+	digest: D,
+	mode: &str,
+	input: &[u8],
+	salt: &[u8],
+	info: &[u8],
+	key_length: usize,
+) -> Result<Vec<u8>, String> {
+	let extract = |ikm: &[u8]| {
+		let mut prk = vec![0u8; digest.output_bytes()];
+		hkdf_extract(digest.clone(), salt, ikm, &mut prk);
+		prk
+	};
+	let expand = |prk: &[u8]| {
+		if key_length > 255 * digest.output_bytes() {
+			return Err("Invalid key length (too long for the algorithm)".to_string());
+		}
+		let mut okm = vec![0u8; key_length];
+		hkdf_expand(digest.clone(), prk, info, &mut okm);
+		Ok(okm)
+	};
+
+	match mode {
+		"full" => expand(&extract(input)),
+		"extract" => Ok(extract(input)),
+		"expand" => expand(input),
+		_ => Err("Invalid mode".to_string()),
+	}
+}
+
+fn scrypt(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = get_input(matches)?;
+	let salt = get_hex_arg(matches, "SALT", "Invalid salt")?;
+	let key_length = get_key_length(matches)?;
+
+	let cost = get_number_arg::<u64>(matches, "COST", "Invalid cost")?;
+	if cost < 2 || !cost.is_power_of_two() {
+		return Err("Invalid cost (must be a power of 2)".to_string());
+	}
+	let log_n = cost.trailing_zeros() as u8;
+	let r = get_number_arg::<u32>(matches, "BLOCK_SIZE", "Invalid block size")?;
+	let p = get_number_arg::<u32>(matches, "PARALLELISM", "Invalid parallelism")?;
+
+	// the output length is set by the result buffer, any dkLen is allowed (RFC 7914)
+	let params = scrypt::Params::new(log_n, r, p, scrypt::Params::RECOMMENDED_LEN)
+		.map_err(|_| "Invalid params".to_string())?;
+
+	let mut result = vec![0u8; key_length];
+	scrypt::scrypt(&input, &salt, &params, &mut result)
+		.map_err(|_| "Invalid key length".to_string())?;
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn argon2(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = get_input(matches)?;
+	let salt = get_hex_arg(matches, "SALT", "Invalid salt")?;
+	let secret = get_hex_arg(matches, "SECRET", "Invalid secret")?;
+	let ad = get_hex_arg(matches, "AD", "Invalid associated data")?;
+	let key_length = get_key_length(matches)?;
+
+	let algorithm = match matches.value_of("ALGORITHM") {
+		Some("argon2id") => argon2::Algorithm::Argon2id,
+		Some("argon2i") => argon2::Algorithm::Argon2i,
+		Some("argon2d") => argon2::Algorithm::Argon2d,
+		_ => return Err("Invalid algorithm".to_string()),
+	};
+
+	let params = ParamsBuilder::new()
+		.m_cost(get_number_arg(matches, "MEMORY", "Invalid memory size")?)
+		.t_cost(get_number_arg(matches, "ITERATIONS", "Invalid iterations")?)
+		.p_cost(get_number_arg(
+			matches,
+			"PARALLELISM",
+			"Invalid parallelism",
+		)?)
+		.data(AssociatedData::new(&ad).map_err(|e| format!("Invalid associated data: {}", e))?)
+		.output_len(key_length)
+		.build()
+		.map_err(|e| format!("Invalid params: {}", e))?;
+
+	let argon2 = Argon2::new_with_secret(&secret, algorithm, argon2::Version::V0x13, params)
+		.map_err(|e| format!("Invalid secret: {}", e))?;
+
+	let mut result = vec![0u8; key_length];
+	argon2
+		.hash_password_into(&input, &salt, &mut result)
+		.map_err(|e| format!("Invalid input: {}", e))?;
+
+	let result = Hex::from(result).into();
+
+	Ok(vec![result])
+}
+
+fn bcrypt(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = get_input(matches)?;
+	let cost = get_number_arg::<u32>(matches, "COST", "Invalid cost")?;
+
+	let salt = match matches.value_of("SALT") {
+		Some(salt) => {
+			let salt: Vec<u8> = salt.parse::<Hex>().map_err(|_| "Invalid salt")?.into();
+			if salt.len() != 16 {
+				return Err("Invalid salt size (should be 16 bytes)".to_string());
+			}
+			let mut salt_arr = [0u8; 16];
+			salt_arr.copy_from_slice(&salt);
+			salt_arr
+		}
+		None => {
+			let mut salt = [0u8; 16];
+			thread_rng().fill_bytes(&mut salt);
+			salt
+		}
+	};
+
+	let result = bcrypt::hash_with_salt(&input, cost, salt)
+		.map_err(|e| format!("Hash failed: {}", e))?
+		.format_for_version(bcrypt::Version::TwoB);
+
+	Ok(vec![result])
+}
+
+fn bcrypt_verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = get_input(matches)?;
+	let hash = matches.value_of("HASH").ok_or("Invalid hash")?;
+
+	let valid = bcrypt::verify(&input, hash).map_err(|e| format!("Invalid hash: {}", e))?;
+	if !valid {
+		return Err("Verify failed".to_string());
+	}
+
+	let result = "true".to_string();
+
+	Ok(vec![result])
+}
+
+fn get_input(matches: &ArgMatches) -> Result<Vec<u8>, String> {
+	let input = base::input_string(matches)?;
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
+	Ok(input)
+}
+
+fn get_hex_arg(matches: &ArgMatches, name: &str, error: &str) -> Result<Vec<u8>, String> {
+	let value = matches.value_of(name).ok_or_else(|| error.to_string())?;
+	let value: Vec<u8> = value.parse::<Hex>().map_err(|_| error.to_string())?.into();
+	Ok(value)
+}
+
+fn get_number_arg<T: std::str::FromStr>(
+	matches: &ArgMatches,
+	name: &str,
+	error: &str,
+) -> Result<T, String> {
+	let value = matches.value_of(name).ok_or_else(|| error.to_string())?;
+	value.parse::<T>().map_err(|_| error.to_string())
+}
+
+/// Key length in bytes
+fn get_key_length(matches: &ArgMatches) -> Result<usize, String> {
+	let key_length = get_number_arg::<usize>(matches, "KEY_LENGTH", "Invalid key length")?;
+	if key_length == 0 {
+		return Err("Invalid key length".to_string());
+	}
+	if key_length % 8 != 0 {
+		return Err("Invalid key length (must be a multiple of 8)".to_string());
+	}
+	Ok(key_length / 8)
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"hkdf",
+				vec![
+					Case {
+						desc: "SHA-2 256 (RFC 5869 test case 1)".to_string(),
+						input: vec![
+							"-s",
+							"0x000102030405060708090a0b0c",
+							"-i",
+							"0xf0f1f2f3f4f5f6f7f8f9",
+							"-l",
+							"336",
+							"0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Extract only".to_string(),
+						input: vec![
+							"-m",
+							"extract",
+							"-s",
+							"0x000102030405060708090a0b0c",
+							"0x0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Expand only".to_string(),
+						input: vec![
+							"-m",
+							"expand",
+							"-i",
+							"0xf0f1f2f3f4f5f6f7f8f9",
+							"-l",
+							"336",
+							"0x077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-1 (RFC 5869 test case 4)".to_string(),
+						input: vec![
+							"-a",
+							"sha1",
+							"-s",
+							"0x000102030405060708090a0b0c",
+							"-i",
+							"0xf0f1f2f3f4f5f6f7f8f9",
+							"-l",
+							"336",
+							"0x0b0b0b0b0b0b0b0b0b0b0b",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"scrypt",
+				vec![
+					Case {
+						desc: "RFC 7914 test vector 2".to_string(),
+						input: vec![
+							"-s",
+							"0x4e61436c",
+							"-n",
+							"1024",
+							"-r",
+							"8",
+							"-p",
+							"16",
+							"-l",
+							"512",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xfdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "RFC 7914 test vector 1".to_string(),
+						input: vec!["-n", "16", "-r", "1", "-p", "1", "-l", "512", "0x"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec![
+							"0x77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "RFC 7914 test vector 3".to_string(),
+						input: vec![
+							"-s",
+							"0x536f6469756d43686c6f72696465",
+							"-n",
+							"16384",
+							"-r",
+							"8",
+							"-p",
+							"1",
+							"-l",
+							"512",
+							"0x706c656173656c65746d65696e",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x7023bdcb3afd7348461c06cd81fd38ebfda8fbba904f8e3ea9b543f6545da1f2d5432955613f0fcf62d49705242a9af9e61e85dc0d651e40dfcf017b45575887",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Key length 64 bits".to_string(),
+						input: vec!["-s", "0x00", "-n", "16", "-r", "1", "-p", "1", "-l", "64", "0x00"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec!["0x0ffb6cdcb6a76c38"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Key length 1024 bits".to_string(),
+						input: vec!["-n", "16", "-r", "1", "-p", "1", "-l", "1024", "0x"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec![
+							"0x77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906ce73206656cf8c1ead7f4f6630d0adae1fd8878b77c3b469db919f01597f613ac2f78aec5a5c67c255833119eb3e66b6977e6e7e32e0857b796dfbbc27e3e076",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"argon2",
+				vec![
+					Case {
+						desc: "Argon2id (RFC 9106)".to_string(),
+						input: vec![
+							"-s",
+							"0x02020202020202020202020202020202",
+							"-m",
+							"32",
+							"-t",
+							"3",
+							"-p",
+							"4",
+							"-k",
+							"0x0303030303030303",
+							"-d",
+							"0x040404040404040404040404",
+							"0x0101010101010101010101010101010101010101010101010101010101010101",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Argon2i (RFC 9106)".to_string(),
+						input: vec![
+							"-a",
+							"argon2i",
+							"-s",
+							"0x02020202020202020202020202020202",
+							"-m",
+							"32",
+							"-t",
+							"3",
+							"-p",
+							"4",
+							"-k",
+							"0x0303030303030303",
+							"-d",
+							"0x040404040404040404040404",
+							"0x0101010101010101010101010101010101010101010101010101010101010101",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xc814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "Argon2d (RFC 9106)".to_string(),
+						input: vec![
+							"-a",
+							"argon2d",
+							"-s",
+							"0x02020202020202020202020202020202",
+							"-m",
+							"32",
+							"-t",
+							"3",
+							"-p",
+							"4",
+							"-k",
+							"0x0303030303030303",
+							"-d",
+							"0x040404040404040404040404",
+							"0x0101010101010101010101010101010101010101010101010101010101010101",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"bcrypt",
+				vec![Case {
+					desc: "Cost 5".to_string(),
+					input: vec![
+						"-c",
+						"5",
+						"-s",
+						"10410410410410410410410410410410",
+						"0x552a55",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec!["$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.13.0".to_string(),
+				}],
+			),
+			(
+				"bcrypt_verify",
+				vec![Case {
+					desc: "$2a$, cost 5".to_string(),
+					input: vec![
+						"-H",
+						"'$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW'",
+						"0x552a55",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec!["true"].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.13.0".to_string(),
+				}],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
@@ -0,0 +1,382 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, hash, pbkdf2, Command, Module};
+use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use rand::{thread_rng, RngCore};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Mnemonic (BIP-39)".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+lazy_static! {
+	static ref WORDS: Vec<&'static str> = include_str!("mnemonic/english.txt").lines().collect();
+	static ref WORD_INDEXES: HashMap<&'static str, usize> =
+		WORDS.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+}
+
+const WORD_COUNTS: [&str; 5] = ["12", "15", "18", "21", "24"];
+
+const SEED_ITERATIONS: u32 = 2048;
+
+const SEED_LENGTH: usize = 64;
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("mnemonic_gen")
+				.about("Generate mnemonic")
+				.arg(
+					Arg::with_name("WORDS")
+						.long("words")
+						.short("w")
+						.help("Word count")
+						.takes_value(true)
+						.possible_values(&WORD_COUNTS)
+						.default_value("12")
+						.required(false),
+				),
+			f: mnemonic_gen,
+		},
+		Command {
+			app: SubCommand::with_name("h2mnemonic")
+				.about("Convert entropy to mnemonic")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Entropy (Hex, 16 / 20 / 24 / 28 / 32 bytes)")
+						.required(false)
+						.index(1),
+				)
+				.arg(base::in_format_arg()),
+			f: h2mnemonic,
+		},
+		Command {
+			app: SubCommand::with_name("mnemonic2h")
+				.about("Convert mnemonic to entropy (with the checksum validated)")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Mnemonic")
+						.required(false)
+						.index(1),
+				)
+				.arg(base::out_format_arg()),
+			f: mnemonic2h,
+		},
+		Command {
+			app: SubCommand::with_name("mnemonic_seed")
+				.about("Convert mnemonic to seed")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Mnemonic")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("PASSPHRASE")
+						.long("passphrase")
+						.short("p")
+						.help("Passphrase")
+						.takes_value(true)
+						.default_value("")
+						.required(false),
+				)
+				.arg(base::out_format_arg()),
+			f: mnemonic_seed,
+		},
+	]
+}
+
+fn mnemonic_gen(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let words = matches.value_of("WORDS").ok_or("Invalid word count")?;
+	let words = words.parse::<usize>().map_err(|_| "Invalid word count")?;
+
+	// every 3 words carry 32 bits of entropy and 1 bit of checksum
+	let mut entropy = vec![0u8; words / 3 * 4];
+	thread_rng().fill_bytes(&mut entropy);
+
+	let result = entropy_to_mnemonic(&entropy)?;
+
+	Ok(vec![result])
+}
+
+fn h2mnemonic(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let entropy: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid entropy")?.into();
+
+	let result = entropy_to_mnemonic(&entropy)?;
+
+	Ok(vec![result])
+}
+
+fn mnemonic2h(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let entropy = mnemonic_to_entropy(&input)?;
+
+	let result = Hex::from(entropy).into();
+
+	Ok(vec![result])
+}
+
+fn mnemonic_seed(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let passphrase = matches.value_of("PASSPHRASE").ok_or("Invalid passphrase")?;
+
+	let seed = to_seed(&input, passphrase)?;
+
+	let result = Hex::from(seed).into();
+
+	Ok(vec![result])
+}
+
+/// BIP-39 seed of the mnemonic, the checksum is validated
+pub fn to_seed(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+	mnemonic_to_entropy(mnemonic)?;
+
+	// BIP-39 requires NFKD, which is the identity for ASCII only
+	if !passphrase.is_ascii() {
+		return Err("Invalid passphrase: only ASCII is supported".to_string());
+	}
+
+	let mnemonic = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
+	let salt = format!("mnemonic{}", passphrase);
+	let iterations = NonZeroU32::new(SEED_ITERATIONS).ok_or("Invalid iterations")?;
+
+	pbkdf2::derive_key(
+		"sha2_512",
+		iterations,
+		salt.as_bytes(),
+		mnemonic.as_bytes(),
+		SEED_LENGTH,
+	)
+}
+
+fn entropy_to_mnemonic(entropy: &[u8]) -> Result<String, String> {
+	if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+		return Err("Invalid entropy: 16 / 20 / 24 / 28 / 32 bytes expected".to_string());
+	}
+
+	let checksum = hash::sha2_256_hash(entropy);
+	let data = [entropy, &checksum].concat();
+
+	let words = entropy.len() * 8 / 11 + 1;
+	let result = (0..words)
+		.map(|i| WORDS[bits_at(&data, i * 11, 11)])
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	Ok(result)
+}
+
+fn mnemonic_to_entropy(mnemonic: &str) -> Result<Vec<u8>, String> {
+	let indexes = mnemonic
+		.split_whitespace()
+		.map(|word| {
+			WORD_INDEXES
+				.get(word)
+				.cloned()
+				.ok_or_else(|| format!("Invalid word: {}", word))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if !matches!(indexes.len(), 12 | 15 | 18 | 21 | 24) {
+		return Err("Invalid word count".to_string());
+	}
+
+	// the checksum is at most 8 bits
+	let entropy_len = indexes.len() / 3 * 4;
+	let mut data = vec![0u8; entropy_len + 1];
+	for (i, index) in indexes.iter().enumerate() {
+		for bit in 0..11 {
+			if index >> (10 - bit) & 1 == 1 {
+				let position = i * 11 + bit;
+				data[position / 8] |= 0x80 >> (position % 8);
+			}
+		}
+	}
+
+	let checksum_bits = entropy_len / 4;
+	let entropy = data[..entropy_len].to_vec();
+	let checksum = hash::sha2_256_hash(&entropy);
+	if bits_at(&data, entropy_len * 8, checksum_bits) != bits_at(&checksum, 0, checksum_bits) {
+		return Err("Invalid checksum".to_string());
+	}
+
+	Ok(entropy)
+}
+
+/// Read `len` bits (big-endian) from the bit offset
+fn bits_at(data: &[u8], offset: usize, len: usize) -> usize {
+	(offset..offset + len).fold(0, |acc, position| {
+		(acc << 1) | ((data[position / 8] >> (7 - position % 8)) & 1) as usize
+	})
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"mnemonic_gen",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["-w", "24"].into_iter().map(Into::into).collect(),
+						output: vec![
+							"explain hunt fashion history twin stay dust couch silk junior list drip gentle bid repair typical tail wall champion identify physical film unfair dynamic",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"h2mnemonic",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec!["0x7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						output: vec![
+							"legal winner thank year wave sausage worth useful legal winner thank yellow",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "24 words".to_string(),
+						input: vec![
+							"0x8080808080808080808080808080808080808080808080808080808080808080",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"mnemonic2h",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec![
+							"'legal winner thank year wave sausage worth useful legal winner thank yellow'",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "24 words".to_string(),
+						input: vec![
+							"'letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless'",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x8080808080808080808080808080808080808080808080808080808080808080",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"mnemonic_seed",
+				vec![
+					Case {
+						desc: "".to_string(),
+						input: vec![
+							"'legal winner thank year wave sausage worth useful legal winner thank yellow'",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x878386efb78845b3355bd15ea4d39ef97d179cb712b77d5c12b6be415fffeffe5f377ba02bf3f8544ab800b955e51fbff09828f682052a20faa6addbbddfb096",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "With passphrase".to_string(),
+						input: vec![
+							"-p",
+							"TREZOR",
+							"'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about'",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xc55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
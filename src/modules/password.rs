@@ -0,0 +1,789 @@
+use crate::modules::base::Hex;
+use crate::modules::{base, pbkdf2, Command, Module};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use clap::{Arg, ArgMatches, SubCommand};
+use rand::{thread_rng, RngCore};
+use scrypt::Scrypt;
+use sha_crypt::{Sha256Params, Sha512Params};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Password hash (PHC string format, modular crypt format)".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+const ALGORITHM_NAMES: [&str; 10] = [
+	"argon2id",
+	"argon2i",
+	"argon2d",
+	"scrypt",
+	"bcrypt",
+	"pbkdf2-sha1",
+	"pbkdf2-sha256",
+	"pbkdf2-sha512",
+	"sha256-crypt",
+	"sha512-crypt",
+];
+
+const ALGORITHM_HELP: &str = "Algorithm (params)
+argon2id: $argon2id$ (m, t, p, l)
+argon2i: $argon2i$ (m, t, p, l)
+argon2d: $argon2d$ (m, t, p, l)
+scrypt: $scrypt$ (ln, r, p, l)
+bcrypt: $2b$ (cost)
+pbkdf2-sha1: $pbkdf2$ (i, l)
+pbkdf2-sha256: $pbkdf2-sha256$ (i, l)
+pbkdf2-sha512: $pbkdf2-sha512$ (i, l)
+sha256-crypt: $5$ (rounds)
+sha512-crypt: $6$ (rounds)
+";
+
+const PHC_SALT_MIN_LEN: usize = 4;
+
+/// glibc truncates longer sha-crypt salts
+const SHA_CRYPT_SALT_MAX_LEN: usize = 16;
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("pw_hash")
+				.about("Password hash to PHC string / modular crypt string")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ALGORITHM")
+						.long("algorithm")
+						.short("a")
+						.help(ALGORITHM_HELP)
+						.takes_value(true)
+						.possible_values(&ALGORITHM_NAMES)
+						.default_value("argon2id")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PARAMS")
+						.long("params")
+						.short("p")
+						.help("Params, e.g. m=19456,t=2,p=1")
+						.takes_value(true)
+						.default_value("")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SALT")
+						.long("salt")
+						.short("s")
+						.help("Salt (Hex, random if not given)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(base::in_format_arg()),
+			f: pw_hash,
+		},
+		Command {
+			app: SubCommand::with_name("pw_verify")
+				.about("Password verify with PHC string / modular crypt string")
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Password (Hex)")
+						.required(false)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.short("H")
+						.help("Hash (e.g. $argon2id$v=19$..., $2b$12$..., $pbkdf2-sha256$..., $6$...)")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(base::in_format_arg()),
+			f: pw_verify,
+		},
+	]
+}
+
+fn pw_hash(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
+
+	let algorithm = matches.value_of("ALGORITHM").ok_or("Invalid algorithm")?;
+
+	let params = matches.value_of("PARAMS").ok_or("Invalid params")?;
+	let params = Params::parse(params)?;
+
+	let salt = match matches.value_of("SALT") {
+		Some(salt) => salt.parse::<Hex>().map_err(|_| "Invalid salt")?.into(),
+		None => random_salt(algorithm),
+	};
+
+	let result = match algorithm {
+		"argon2id" | "argon2i" | "argon2d" => argon2_hash(algorithm, &input, &salt, &params),
+		"scrypt" => scrypt_hash(&input, &salt, &params),
+		"bcrypt" => bcrypt_hash(&input, &salt, &params),
+		"pbkdf2-sha1" | "pbkdf2-sha256" | "pbkdf2-sha512" => {
+			pbkdf2_hash(algorithm, &input, &salt, &params)
+		}
+		"sha256-crypt" | "sha512-crypt" => sha_crypt_hash(algorithm, &input, &salt, &params),
+		_ => Err("Invalid algorithm".to_string()),
+	}?;
+
+	Ok(vec![result])
+}
+
+fn pw_verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Invalid input")?.into();
+
+	let hash = matches.value_of("HASH").ok_or("Invalid hash")?;
+
+	let id = hash.split('$').nth(1).ok_or("Invalid hash")?;
+
+	let valid = match id {
+		"argon2id" | "argon2i" | "argon2d" | "scrypt" => phc_verify(&input, hash),
+		"2a" | "2b" | "2x" | "2y" => {
+			bcrypt::verify(&input, hash).map_err(|e| format!("Invalid hash: {}", e))
+		}
+		"pbkdf2" | "pbkdf2-sha256" | "pbkdf2-sha512" => pbkdf2_verify(&input, hash),
+		"5" | "6" => sha_crypt_verify(&input, hash),
+		_ => Err(format!("Invalid hash: unsupported identifier ${}$", id)),
+	}?;
+
+	if !valid {
+		return Err("Verify failed".to_string());
+	}
+
+	let result = "true".to_string();
+
+	Ok(vec![result])
+}
+
+/// Params of the form k1=v1,k2=v2
+struct Params(HashMap<String, String>);
+
+impl Params {
+	fn parse(s: &str) -> Result<Self, String> {
+		let params = s
+			.split(',')
+			.filter(|x| !x.is_empty())
+			.map(|x| {
+				let mut kv = x.splitn(2, '=');
+				match (kv.next(), kv.next()) {
+					(Some(k), Some(v)) => Ok((k.trim().to_string(), v.trim().to_string())),
+					_ => Err(format!("Invalid params: {}", x)),
+				}
+			})
+			.collect::<Result<HashMap<_, _>, _>>()?;
+		Ok(Params(params))
+	}
+
+	fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+		match self.0.get(key) {
+			Some(v) => v.parse().map_err(|_| format!("Invalid params: {}", key)),
+			None => Ok(default),
+		}
+	}
+}
+
+fn random_salt(algorithm: &str) -> Vec<u8> {
+	match algorithm {
+		// sha-crypt salt is a string of the crypt base64 alphabet
+		"sha256-crypt" | "sha512-crypt" => {
+			const ALPHABET: &[u8] =
+				b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+			let mut salt = [0u8; 16];
+			thread_rng().fill_bytes(&mut salt);
+			salt.iter()
+				.map(|x| ALPHABET[*x as usize % ALPHABET.len()])
+				.collect()
+		}
+		_ => {
+			let mut salt = vec![0u8; 16];
+			thread_rng().fill_bytes(&mut salt);
+			salt
+		}
+	}
+}
+
+fn argon2_hash(
+	algorithm: &str,
+	input: &[u8],
+	salt: &[u8],
+	params: &Params,
+) -> Result<String, String> {
+	let algorithm = algorithm
+		.parse::<argon2::Algorithm>()
+		.map_err(|_| "Invalid algorithm")?;
+	let argon2_params = argon2::Params::new(
+		params.get("m", argon2::Params::DEFAULT_M_COST)?,
+		params.get("t", argon2::Params::DEFAULT_T_COST)?,
+		params.get("p", argon2::Params::DEFAULT_P_COST)?,
+		Some(params.get("l", argon2::Params::DEFAULT_OUTPUT_LEN)?),
+	)
+	.map_err(|e| format!("Invalid params: {}", e))?;
+
+	let salt = phc_salt(salt)?;
+
+	let result = Argon2::new(algorithm, argon2::Version::V0x13, argon2_params)
+		.hash_password(input, &salt)
+		.map_err(|e| format!("Hash failed: {}", e))?;
+
+	Ok(result.to_string())
+}
+
+fn scrypt_hash(input: &[u8], salt: &[u8], params: &Params) -> Result<String, String> {
+	let scrypt_params = scrypt::Params::new(
+		params.get("ln", scrypt::Params::RECOMMENDED_LOG_N)?,
+		params.get("r", scrypt::Params::RECOMMENDED_R)?,
+		params.get("p", scrypt::Params::RECOMMENDED_P)?,
+		params.get("l", scrypt::Params::RECOMMENDED_LEN)?,
+	)
+	.map_err(|e| format!("Invalid params: {}", e))?;
+
+	let salt = phc_salt(salt)?;
+
+	let result = Scrypt
+		.hash_password_customized(input, None, None, scrypt_params, &salt)
+		.map_err(|e| format!("Hash failed: {}", e))?;
+
+	Ok(result.to_string())
+}
+
+fn phc_salt(salt: &[u8]) -> Result<SaltString, String> {
+	// shorter salts make the password-hash crate panic
+	if salt.len() < PHC_SALT_MIN_LEN {
+		return Err(format!(
+			"Invalid salt: should be at least {} bytes",
+			PHC_SALT_MIN_LEN
+		));
+	}
+	SaltString::encode_b64(salt).map_err(|e| format!("Invalid salt: {}", e))
+}
+
+fn phc_verify(input: &[u8], hash: &str) -> Result<bool, String> {
+	let hash = PasswordHash::new(hash).map_err(|e| format!("Invalid hash: {}", e))?;
+	if hash.hash.is_none() {
+		return Err("Invalid hash: no hash".to_string());
+	}
+
+	let result = match hash.algorithm.as_str() {
+		"scrypt" => Scrypt.verify_password(input, &hash),
+		_ => Argon2::default().verify_password(input, &hash),
+	};
+
+	match result {
+		Ok(()) => Ok(true),
+		Err(argon2::password_hash::Error::Password) => Ok(false),
+		Err(e) => Err(format!("Invalid hash: {}", e)),
+	}
+}
+
+fn bcrypt_hash(input: &[u8], salt: &[u8], params: &Params) -> Result<String, String> {
+	if salt.len() != 16 {
+		return Err("Invalid salt size (should be 16 bytes)".to_string());
+	}
+	let mut salt_arr = [0u8; 16];
+	salt_arr.copy_from_slice(salt);
+
+	let result = bcrypt::hash_with_salt(input, params.get("cost", bcrypt::DEFAULT_COST)?, salt_arr)
+		.map_err(|e| format!("Hash failed: {}", e))?
+		.format_for_version(bcrypt::Version::TwoB);
+
+	Ok(result)
+}
+
+/// PBKDF2 algorithm: (PHC identifier, pbkdf2 algorithm, default iterations, default key length)
+fn pbkdf2_algorithm(id: &str) -> Result<(&'static str, &'static str, u32, usize), String> {
+	match id {
+		"pbkdf2-sha1" | "pbkdf2" => Ok(("pbkdf2", "sha1", 1_300_000, 20)),
+		"pbkdf2-sha256" => Ok(("pbkdf2-sha256", "sha2_256", 600_000, 32)),
+		"pbkdf2-sha512" => Ok(("pbkdf2-sha512", "sha2_512", 210_000, 64)),
+		_ => Err("Invalid algorithm".to_string()),
+	}
+}
+
+fn pbkdf2_hash(
+	algorithm: &str,
+	input: &[u8],
+	salt: &[u8],
+	params: &Params,
+) -> Result<String, String> {
+	let (id, algo, default_iterations, default_key_length) = pbkdf2_algorithm(algorithm)?;
+
+	let iterations = params.get("i", default_iterations)?;
+	let iterations = NonZeroU32::new(iterations).ok_or("Invalid params: i")?;
+	let key_length = params.get("l", default_key_length)?;
+	if key_length == 0 {
+		return Err("Invalid params: l".to_string());
+	}
+
+	let result = pbkdf2::derive_key(algo, iterations, salt, input, key_length)?;
+
+	Ok(format!(
+		"${}$i={},l={}${}${}",
+		id,
+		iterations,
+		key_length,
+		base64::encode_config(salt, base64::STANDARD_NO_PAD),
+		base64::encode_config(&result, base64::STANDARD_NO_PAD)
+	))
+}
+
+/// Supports both the PHC form `$pbkdf2-sha256$i=<iterations>,l=<length>$<salt>$<hash>`
+/// and the passlib form `$pbkdf2-sha256$<iterations>$<salt>$<hash>` (adapted base64)
+fn pbkdf2_verify(input: &[u8], hash: &str) -> Result<bool, String> {
+	let invalid = |e: &str| format!("Invalid hash: {}", e);
+
+	let parts = hash.split('$').collect::<Vec<_>>();
+	let (id, params, salt, expected) = match parts.as_slice() {
+		["", id, params, salt, expected] => (*id, *params, *salt, *expected),
+		_ => return Err(invalid("format")),
+	};
+	let (_, algo, _, _) = pbkdf2_algorithm(id)?;
+
+	let decode = |x: &str| base64::decode_config(&x.replace('.', "+"), base64::STANDARD_NO_PAD);
+	let salt = decode(salt).map_err(|_| invalid("salt"))?;
+	let expected = decode(expected).map_err(|_| invalid("hash"))?;
+	// an empty hash would match any password
+	if expected.is_empty() {
+		return Err(invalid("hash"));
+	}
+
+	let iterations = match params.parse::<u32>() {
+		Ok(iterations) => iterations,
+		Err(_) => Params::parse(params)?.get("i", 0)?,
+	};
+	let iterations = NonZeroU32::new(iterations).ok_or_else(|| invalid("iterations"))?;
+
+	let result = pbkdf2::derive_key(algo, iterations, &salt, input, expected.len())?;
+
+	Ok(result == expected)
+}
+
+fn sha_crypt_hash(
+	algorithm: &str,
+	input: &[u8],
+	salt: &[u8],
+	params: &Params,
+) -> Result<String, String> {
+	let rounds = params.get("rounds", sha_crypt::ROUNDS_DEFAULT)?;
+	let rounds = sha_crypt_rounds(rounds);
+	let salt = &salt[..salt.len().min(SHA_CRYPT_SALT_MAX_LEN)];
+	let salt = String::from_utf8(salt.to_vec()).map_err(|_| "Invalid salt")?;
+
+	let (id, result) = sha_crypt_inner(algorithm, input, &salt, rounds)?;
+
+	// rounds is omitted if it is the default value
+	let rounds = match rounds {
+		sha_crypt::ROUNDS_DEFAULT => "".to_string(),
+		rounds => format!("rounds={}$", rounds),
+	};
+
+	Ok(format!("${}${}{}${}", id, rounds, salt, result))
+}
+
+fn sha_crypt_verify(input: &[u8], hash: &str) -> Result<bool, String> {
+	let invalid = |e: &str| format!("Invalid hash: {}", e);
+
+	let parts = hash.split('$').collect::<Vec<_>>();
+	let (id, rounds, salt, expected) = match parts.as_slice() {
+		["", id, rounds, salt, expected] if rounds.starts_with("rounds=") => {
+			let rounds = rounds
+				.trim_start_matches("rounds=")
+				.parse::<usize>()
+				.map_err(|_| invalid("rounds"))?;
+			(*id, sha_crypt_rounds(rounds), *salt, *expected)
+		}
+		["", id, salt, expected] => (*id, sha_crypt::ROUNDS_DEFAULT, *salt, *expected),
+		_ => return Err(invalid("format")),
+	};
+
+	let algorithm = match id {
+		"5" => "sha256-crypt",
+		_ => "sha512-crypt",
+	};
+
+	let salt = salt
+		.get(..salt.len().min(SHA_CRYPT_SALT_MAX_LEN))
+		.ok_or_else(|| invalid("salt"))?;
+	let (_, result) = sha_crypt_inner(algorithm, input, salt, rounds)?;
+
+	Ok(result == expected)
+}
+
+/// Out of range rounds are clamped as glibc does
+fn sha_crypt_rounds(rounds: usize) -> usize {
+	rounds.clamp(sha_crypt::ROUNDS_MIN, sha_crypt::ROUNDS_MAX)
+}
+
+/// Returns (identifier, hash)
+fn sha_crypt_inner(
+	algorithm: &str,
+	input: &[u8],
+	salt: &str,
+	rounds: usize,
+) -> Result<(&'static str, String), String> {
+	let invalid_rounds = |_| "Invalid rounds (should be 1000 ~ 999999999)".to_string();
+	let hash_failed = |_| "Hash failed".to_string();
+	match algorithm {
+		"sha256-crypt" => {
+			let params = Sha256Params::new(rounds).map_err(invalid_rounds)?;
+			let result = sha_crypt::sha256_crypt_b64(input, salt.as_bytes(), &params)
+				.map_err(hash_failed)?;
+			Ok(("5", result))
+		}
+		"sha512-crypt" => {
+			let params = Sha512Params::new(rounds).map_err(invalid_rounds)?;
+			let result = sha_crypt::sha512_crypt_b64(input, salt.as_bytes(), &params)
+				.map_err(hash_failed)?;
+			Ok(("6", result))
+		}
+		_ => Err("Invalid algorithm".to_string()),
+	}
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![
+			(
+				"pw_hash",
+				vec![
+					Case {
+						desc: "Argon2i".to_string(),
+						input: vec![
+							"-a",
+							"argon2i",
+							"-p",
+							"m=65536,t=2,p=4,l=24",
+							"-s",
+							"0x736f6d6573616c74",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$argon2i$v=19$m=65536,t=2,p=4$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "scrypt".to_string(),
+						input: vec![
+							"-a",
+							"scrypt",
+							"-p",
+							"ln=10,r=8,p=1",
+							"-s",
+							"0x736f6d6573616c74736f6d6573616c74",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$scrypt$ln=10,r=8,p=1$c29tZXNhbHRzb21lc2FsdA$dj05BT7oUTq35qmxXqG/pksYG8IJr8uxtvAzbfGjoic",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "bcrypt".to_string(),
+						input: vec![
+							"-a",
+							"bcrypt",
+							"-p",
+							"cost=5",
+							"-s",
+							"10410410410410410410410410410410",
+							"0x552a55",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "PBKDF2 SHA-2 256".to_string(),
+						input: vec![
+							"-a",
+							"pbkdf2-sha256",
+							"-p",
+							"i=1000",
+							"-s",
+							"0x73616c7473616c7473616c7473616c74",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$pbkdf2-sha256$i=1000,l=32$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-512 crypt".to_string(),
+						input: vec![
+							"-a",
+							"sha512-crypt",
+							"-s",
+							"0x73616c74737472696e67",
+							"0x48656c6c6f20776f726c6421",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-256 crypt".to_string(),
+						input: vec![
+							"-a",
+							"sha256-crypt",
+							"-s",
+							"0x73616c74737472696e67",
+							"0x48656c6c6f20776f726c6421",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-512 crypt, the salt is truncated to 16 characters".to_string(),
+						input: vec![
+							"-a",
+							"sha512-crypt",
+							"-p",
+							"rounds=10000",
+							"-s",
+							"0x73616c74737472696e6773616c74737472696e67",
+							"0x48656c6c6f20776f726c6421",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v.",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-512 crypt, the rounds are clamped to 1000".to_string(),
+						input: vec![
+							"-a",
+							"sha512-crypt",
+							"-p",
+							"rounds=10",
+							"-s",
+							"0x726f756e6473746f6f6c6f77",
+							"0x746865206d696e696d756d206e756d626572206973207374696c6c206f62736572766564",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58jaTfF4ZEQpyUNGc0dqbpBYYBaHHrsX.",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+			(
+				"pw_verify",
+				vec![
+					Case {
+						desc: "Argon2i".to_string(),
+						input: vec![
+							"-H",
+							"'$argon2i$v=19$m=65536,t=2,p=4$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG'",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "scrypt".to_string(),
+						input: vec![
+							"-H",
+							"'$scrypt$ln=10,r=8,p=1$c29tZXNhbHRzb21lc2FsdA$dj05BT7oUTq35qmxXqG/pksYG8IJr8uxtvAzbfGjoic'",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "bcrypt".to_string(),
+						input: vec![
+							"-H",
+							"'$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW'",
+							"0x552a55",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "PBKDF2 SHA-2 256 (passlib)".to_string(),
+						input: vec![
+							"-H",
+							"'$pbkdf2-sha256$1000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA'",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "PBKDF2 SHA-1 (passlib)".to_string(),
+						input: vec![
+							"-H",
+							"'$pbkdf2$2000$./8$iREhryLqUT0LDfQxdSyjEDuD/8c'",
+							"0x70617373776f7264",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "SHA-512 crypt".to_string(),
+						input: vec![
+							"-H",
+							"'$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v.'",
+							"0x48656c6c6f20776f726c6421",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["true"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
+			),
+		]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::base::test::test_module;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+
+	#[test]
+	fn test_short_salt() {
+		let params = Params::parse("").unwrap();
+		for salt in [&[0u8][..], &[0u8; 3][..]].iter() {
+			assert!(argon2_hash("argon2id", b"abc", salt, &params).is_err());
+			assert!(scrypt_hash(b"abc", salt, &params).is_err());
+		}
+	}
+
+	#[test]
+	fn test_pbkdf2_empty_hash() {
+		let params = Params::parse("i=1,l=0").unwrap();
+		assert_eq!(
+			pbkdf2_hash("pbkdf2-sha256", b"a", &[1, 2, 3, 4, 5, 6, 7, 8], &params),
+			Err("Invalid params: l".to_string())
+		);
+		for hash in [
+			"$pbkdf2-sha256$1$AQIDBAUGBwg$",
+			"$pbkdf2-sha256$i=1,l=0$AQIDBAUGBwg$",
+		]
+		.iter()
+		{
+			assert_eq!(
+				pbkdf2_verify(b"a", hash),
+				Err("Invalid hash: hash".to_string())
+			);
+		}
+	}
+}
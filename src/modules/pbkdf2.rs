@@ -51,7 +51,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("INPUT")
 						.help("Secret (Hex)")
 						.required(false)
-						.index(1)),
+						.index(1))
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 
 			f: pbkdf2,
 		}
@@ -59,13 +61,7 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 }
 
 fn pbkdf2(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let algo = match matches.value_of("ALGORITHM") {
-		Some("sha1") => PBKDF2_HMAC_SHA1,
-		Some("sha2_256") => PBKDF2_HMAC_SHA256,
-		Some("sha2_384") => PBKDF2_HMAC_SHA384,
-		Some("sha2_512") => PBKDF2_HMAC_SHA512,
-		_ => return Err("Invalid algorithm".to_string()),
-	};
+	let algo = matches.value_of("ALGORITHM").ok_or("Invalid algorithm")?;
 
 	let iterations = match matches.value_of("ITERATIONS") {
 		Some(iterations) => iterations
@@ -110,15 +106,36 @@ fn pbkdf2(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let secret = base::input_string(matches)?;
 	let secret: Vec<u8> = secret.parse::<Hex>().map_err(|_| "Invalid secret")?.into();
 
-	let mut result = vec![0u8; key_byte_length as usize];
-
-	derive(algo, iterations, &salt, &secret, &mut result);
+	let result = derive_key(algo, iterations, &salt, &secret, key_byte_length as usize)?;
 
 	let result = Hex::from(result).into();
 
 	Ok(vec![result])
 }
 
+/// Derive a key with the given algorithm (sha1, sha2_256, sha2_384, sha2_512)
+pub fn derive_key(
+	algo: &str,
+	iterations: NonZeroU32,
+	salt: &[u8],
+	secret: &[u8],
+	key_byte_length: usize,
+) -> Result<Vec<u8>, String> {
+	let algo = match algo {
+		"sha1" => PBKDF2_HMAC_SHA1,
+		"sha2_256" => PBKDF2_HMAC_SHA256,
+		"sha2_384" => PBKDF2_HMAC_SHA384,
+		"sha2_512" => PBKDF2_HMAC_SHA512,
+		_ => return Err("Invalid algorithm".to_string()),
+	};
+
+	let mut result = vec![0u8; key_byte_length];
+
+	derive(algo, iterations, salt, secret, &mut result);
+
+	Ok(result)
+}
+
 mod cases {
 	use crate::modules::Case;
 	use linked_hash_map::LinkedHashMap;
@@ -0,0 +1,183 @@
+use crate::modules::{base, Command};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use linked_hash_map::LinkedHashMap;
+
+pub fn app<'a, 'b>() -> App<'a, 'b> {
+	SubCommand::with_name("pipe")
+		.about("Run sub commands as a pipeline (the output of each stage is the input of the next)")
+		.arg(
+			Arg::with_name("STAGES")
+				.help("Stages, e.g. 's2h hello' 'h2b64'")
+				.multiple(true)
+				.required(true),
+		)
+		.arg(base::out_format_arg())
+}
+
+pub fn run<'a, 'b>(
+	matches: &ArgMatches,
+	commands: &LinkedHashMap<String, Command<'a, 'b>>,
+) -> Result<Vec<String>, String> {
+	let mut stages = matches
+		.values_of("STAGES")
+		.ok_or_else(|| "Invalid stages".to_string())?;
+
+	let result = stages.try_fold(None, |input, stage| {
+		run_stage(stage, input, commands).map(Some)
+	})?;
+
+	Ok(result.unwrap_or_default())
+}
+
+fn run_stage<'a, 'b>(
+	stage: &str,
+	input: Option<Vec<String>>,
+	commands: &LinkedHashMap<String, Command<'a, 'b>>,
+) -> Result<Vec<String>, String> {
+	let args = split_args(stage)?;
+
+	let name = args
+		.first()
+		.cloned()
+		.ok_or_else(|| "Invalid stage: empty".to_string())?;
+	let command = commands
+		.get(&name)
+		.ok_or_else(|| format!("Invalid stage: {}: no such sub command", name))?;
+
+	let matches = command
+		.app
+		.clone()
+		.get_matches_from_safe(&args)
+		.map_err(|e| format!("Invalid stage: {}: {}", name, e.message))?;
+	// the output of a stage is the input of the next, only the whole pipe can be formatted
+	if matches.is_present("OUT_FORMAT") {
+		return Err(format!(
+			"Invalid stage: {}: --out-format is only supported on pipe itself",
+			name
+		));
+	}
+
+	let run = || (command.f)(&matches).map_err(|e| format!("{}: {}", name, e));
+
+	// feed the output of the previous stage as stdin, like a shell pipe
+	match input {
+		Some(input) => match input.as_slice() {
+			[input] => base::with_stdin(format!("{}\n", input).into_bytes(), run),
+			_ => Err(format!(
+				"Invalid stage: {}: the previous stage should give one output, got {}",
+				name,
+				input.len()
+			)),
+		},
+		None => run(),
+	}
+}
+
+/// Split a stage into args
+/// Supports single quotes, double quotes and backslash escapes
+fn split_args(stage: &str) -> Result<Vec<String>, String> {
+	let mut result = vec![];
+	let mut current: Option<String> = None;
+	let mut quote: Option<char> = None;
+	let mut chars = stage.chars();
+
+	while let Some(c) = chars.next() {
+		match (quote, c) {
+			(Some(q), c) if c == q => quote = None,
+			(Some('"'), '\\') | (None, '\\') => {
+				let c = chars
+					.next()
+					.ok_or_else(|| format!("Invalid stage: {}: trailing backslash", stage))?;
+				current.get_or_insert_with(String::new).push(c);
+			}
+			(Some(_), c) => current.get_or_insert_with(String::new).push(c),
+			(None, '\'') | (None, '"') => {
+				quote = Some(c);
+				current.get_or_insert_with(String::new);
+			}
+			(None, c) if c.is_whitespace() => {
+				if let Some(arg) = current.take() {
+					result.push(arg);
+				}
+			}
+			(None, c) => current.get_or_insert_with(String::new).push(c),
+		}
+	}
+
+	if quote.is_some() {
+		return Err(format!("Invalid stage: {}: unclosed quote", stage));
+	}
+	if let Some(arg) = current {
+		result.push(arg);
+	}
+
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::ModuleManager;
+
+	fn pipe(stages: Vec<&str>) -> Result<Vec<String>, String> {
+		let mm = ModuleManager::new();
+		let matches = app().get_matches_from(vec!["pipe"].into_iter().chain(stages));
+		run(&matches, &mm.commands)
+	}
+
+	#[test]
+	fn test_pipe() {
+		assert_eq!(pipe(vec!["s2h abc", "h2b64"]), Ok(vec!["YWJj".to_string()]));
+		assert_eq!(
+			pipe(vec!["s2h 'hello world'", "hash -a md5", "h2b58"]),
+			Ok(vec!["ChLQMLdGMFWDeWrG29QYZp".to_string()])
+		);
+		assert_eq!(pipe(vec!["s2h abc", "h2s"]), Ok(vec!["abc".to_string()]));
+		// stages with their own input or without INPUT ignore the output of the previous stage
+		assert_eq!(
+			pipe(vec!["s2h abc", "s2h def"]),
+			Ok(vec!["0x646566".to_string()])
+		);
+		assert_eq!(
+			pipe(vec![
+				"s2h abc",
+				"ed_pk -m 0xb850164d1feec8698acca329947c9885bd1d94034d2fbbe6080598adbe15b298"
+			]),
+			Ok(vec![
+				"0x892c89a4cd631d08da314607223814775604535a05f50e959d21209d01740eba".to_string()
+			])
+		);
+		assert_eq!(
+			pipe(vec!["hash -a md5 -f Cargo.toml Cargo.toml", "h2b64"]),
+			Err(
+				"Invalid stage: h2b64: the previous stage should give one output, got 2"
+					.to_string()
+			)
+		);
+		// "-" reads the output of the previous stage as well
+		assert_eq!(
+			pipe(vec!["s2h abc", "hash -a md5 -f -"]),
+			Ok(vec!["30288b797c70d51ebf81bce9dfa53255  -".to_string()])
+		);
+		assert_eq!(
+			pipe(vec!["s2h abc", "hash -a md5 --out-format raw"]),
+			Err("Invalid stage: hash: --out-format is only supported on pipe itself".to_string())
+		);
+		assert_eq!(
+			pipe(vec!["s2h abc", "no_such_command"]),
+			Err("Invalid stage: no_such_command: no such sub command".to_string())
+		);
+	}
+
+	#[test]
+	fn test_split_args() {
+		assert_eq!(
+			split_args(r#"hash -a md5 'a b' "c \"d\"" e\ f"#),
+			Ok(vec!["hash", "-a", "md5", "a b", "c \"d\"", "e f"]
+				.into_iter()
+				.map(Into::into)
+				.collect())
+		);
+		assert!(split_args("s2h 'abc").is_err());
+	}
+}
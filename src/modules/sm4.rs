@@ -1,5 +1,6 @@
-use self::Mode::CTR;
+use self::Mode::{CBC, CFB, CTR, ECB, GCM, OFB};
 use crate::modules::base::Hex;
+use crate::modules::block_mode::{self, Block, Padding};
 use crate::modules::{base, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
 use yogcrypt::sm4;
@@ -27,9 +28,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("MODE")
 						.long("mode")
 						.short("m")
-						.help("Mode\nctr: CTR\n")
+						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\ncfb: CFB\nofb: OFB\ngcm: GCM\n")
 						.takes_value(true)
-						.possible_values(&["ctr"])
+						.possible_values(&["ecb", "cbc", "ctr", "cfb", "ofb", "gcm"])
 						.required(true),
 				)
 				.arg(
@@ -47,7 +48,45 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("IV (Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("PADDING")
+						.long("padding")
+						.short("p")
+						.help(block_mode::PADDING_HELP)
+						.takes_value(true)
+						.possible_values(&block_mode::PADDING_NAMES)
+						.default_value("pkcs7")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce for gcm (Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for gcm (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TAG_LENGTH")
+						.long("tag-length")
+						.short("t")
+						.help("Tag length for gcm (bits, the tag is appended to the cipher)")
+						.takes_value(true)
+						.default_value("128")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: sm4_enc,
 		},
 		Command {
@@ -63,9 +102,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("MODE")
 						.long("mode")
 						.short("m")
-						.help("Mode\nctr: CTR\n")
+						.help("Mode\necb: ECB\ncbc: CBC\nctr: CTR\ncfb: CFB\nofb: OFB\ngcm: GCM\n")
 						.takes_value(true)
-						.possible_values(&["ctr"])
+						.possible_values(&["ecb", "cbc", "ctr", "cfb", "ofb", "gcm"])
 						.required(true),
 				)
 				.arg(
@@ -83,14 +122,72 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("IV (Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(
+					Arg::with_name("PADDING")
+						.long("padding")
+						.short("p")
+						.help(block_mode::PADDING_HELP)
+						.takes_value(true)
+						.possible_values(&block_mode::PADDING_NAMES)
+						.default_value("pkcs7")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.short("n")
+						.help("Nonce for gcm (Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("AAD")
+						.long("aad")
+						.short("a")
+						.help("Additional authenticated data for gcm (Hex)")
+						.takes_value(true)
+						.default_value("0x")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TAG_LENGTH")
+						.long("tag-length")
+						.short("t")
+						.help("Tag length for gcm (bits, the tag is appended to the cipher)")
+						.takes_value(true)
+						.default_value("128")
+						.required(false),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: sm4_dec,
 		},
 	]
 }
 
 enum Mode {
-	CTR { iv: Vec<u8> },
+	ECB {
+		padding: Padding,
+	},
+	CBC {
+		iv: Vec<u8>,
+		padding: Padding,
+	},
+	CTR {
+		iv: Vec<u8>,
+	},
+	CFB {
+		iv: Vec<u8>,
+	},
+	OFB {
+		iv: Vec<u8>,
+	},
+	GCM {
+		nonce: Vec<u8>,
+		aad: Vec<u8>,
+		tag_len: usize,
+	},
 }
 
 enum KeySize {
@@ -98,11 +195,20 @@ enum KeySize {
 }
 
 fn sm4_enc(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let (key_size, key, mode, input) = get_common_arg(matches)?;
+	let (_, key, mode, input) = get_common_arg(matches)?;
 
 	// cipher
 	let result = match mode {
-		CTR { iv } => sm4_enc_ctr(key_size, &key, &input, &iv),
+		ECB { padding } => block_mode::ecb_encrypt(sm4_block_enc(&key), &input, padding),
+		CBC { iv, padding } => block_mode::cbc_encrypt(sm4_block_enc(&key), &iv, &input, padding),
+		CTR { iv } => block_mode::ctr_process(sm4_block_enc(&key), &iv, &input),
+		CFB { iv } => block_mode::cfb_encrypt(sm4_block_enc(&key), &iv, &input),
+		OFB { iv } => block_mode::ofb_process(sm4_block_enc(&key), &iv, &input),
+		GCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::gcm_encrypt(sm4_block_enc(&key), &nonce, &aad, &input, tag_len),
 	}?;
 	let result = Hex::from(result).into();
 
@@ -110,11 +216,20 @@ fn sm4_enc(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn sm4_dec(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let (key_size, key, mode, input) = get_common_arg(matches)?;
+	let (_, key, mode, input) = get_common_arg(matches)?;
 
 	// plain
 	let result = match mode {
-		CTR { iv } => sm4_dec_ctr(key_size, &key, &input, &iv),
+		ECB { padding } => block_mode::ecb_decrypt(sm4_block_dec(&key), &input, padding),
+		CBC { iv, padding } => block_mode::cbc_decrypt(sm4_block_dec(&key), &iv, &input, padding),
+		CTR { iv } => block_mode::ctr_process(sm4_block_enc(&key), &iv, &input),
+		CFB { iv } => block_mode::cfb_decrypt(sm4_block_enc(&key), &iv, &input),
+		OFB { iv } => block_mode::ofb_process(sm4_block_enc(&key), &iv, &input),
+		GCM {
+			nonce,
+			aad,
+			tag_len,
+		} => block_mode::gcm_decrypt(sm4_block_enc(&key), &nonce, &aad, &input, tag_len),
 	}?;
 	let result = Hex::from(result).into();
 
@@ -142,12 +257,62 @@ fn get_common_arg(matches: &ArgMatches) -> Result<(KeySize, Vec<u8>, Mode, Vec<u
 		Ok(iv)
 	};
 
+	let get_padding = || -> Result<Padding, String> {
+		let padding = matches
+			.value_of("PADDING")
+			.ok_or_else(|| "Invalid padding".to_string())?;
+		padding.parse::<Padding>()
+	};
+
+	let get_nonce = || -> Result<Vec<u8>, String> {
+		let nonce = matches
+			.value_of("NONCE")
+			.ok_or_else(|| "Invalid nonce".to_string())?;
+		let nonce: Vec<u8> = nonce.parse::<Hex>().map_err(|_| "Invalid nonce")?.into();
+		Ok(nonce)
+	};
+
+	let get_aad = || -> Result<Vec<u8>, String> {
+		let aad = matches
+			.value_of("AAD")
+			.ok_or_else(|| "Invalid AAD".to_string())?;
+		let aad: Vec<u8> = aad.parse::<Hex>().map_err(|_| "Invalid AAD")?.into();
+		Ok(aad)
+	};
+
+	let get_tag_len = || -> Result<usize, String> {
+		let tag_length = matches
+			.value_of("TAG_LENGTH")
+			.ok_or_else(|| "Invalid tag length".to_string())?;
+		let tag_length = tag_length
+			.parse::<usize>()
+			.map_err(|_| "Invalid tag length")?;
+		if tag_length % 8 != 0 {
+			return Err("Invalid tag length (must be a multiple of 8)".to_string());
+		}
+		Ok(tag_length / 8)
+	};
+
 	// mode
 	let mode = matches
 		.value_of("MODE")
 		.ok_or_else(|| "Invalid mode".to_string())?;
 	let mode = match mode {
+		"ecb" => ECB {
+			padding: get_padding()?,
+		},
+		"cbc" => CBC {
+			iv: get_iv()?,
+			padding: get_padding()?,
+		},
 		"ctr" => CTR { iv: get_iv()? },
+		"cfb" => CFB { iv: get_iv()? },
+		"ofb" => OFB { iv: get_iv()? },
+		"gcm" => GCM {
+			nonce: get_nonce()?,
+			aad: get_aad()?,
+			tag_len: get_tag_len()?,
+		},
 		_ => unreachable!(),
 	};
 
@@ -157,79 +322,22 @@ fn get_common_arg(matches: &ArgMatches) -> Result<(KeySize, Vec<u8>, Mode, Vec<u
 	Ok((key_size, key, mode, input))
 }
 
-const BLOCK_SIZE: usize = 16;
-
-fn sm4_enc_ctr(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	sm4_ctr_process(key_size, key, input, iv)
+/// SM4 block encrypt function for the shared block modes
+fn sm4_block_enc(key: &[u8]) -> impl Fn(&Block) -> Block {
+	let key = to_key(key);
+	move |block| sm4::sm4_enc(&key, block)
 }
 
-fn sm4_dec_ctr(key_size: KeySize, key: &[u8], input: &[u8], iv: &[u8]) -> Result<Vec<u8>, String> {
-	sm4_ctr_process(key_size, key, input, iv)
+/// SM4 block decrypt function for the shared block modes
+fn sm4_block_dec(key: &[u8]) -> impl Fn(&Block) -> Block {
+	let key = to_key(key);
+	move |block| sm4::sm4_dec(&key, block)
 }
 
-fn sm4_ctr_process(
-	_key_size: KeySize,
-	key: &[u8],
-	input: &[u8],
-	iv: &[u8],
-) -> Result<Vec<u8>, String> {
-	let mut buff = [0u8; BLOCK_SIZE];
-	buff.copy_from_slice(iv);
-
-	let block_count = input.len() / BLOCK_SIZE;
-	let tail_len = input.len() % BLOCK_SIZE;
-
-	let key = {
-		let mut key_arr = [0u8; BLOCK_SIZE];
-		key_arr.copy_from_slice(key);
-		key_arr
-	};
-
-	let mut result = vec![0u8; input.len()];
-
-	for i in 0..block_count {
-		let enc = sm4::sm4_enc(&key, &buff);
-		let ct = block_xor(&enc, &input[i * BLOCK_SIZE..i * BLOCK_SIZE + BLOCK_SIZE]);
-
-		let to_write = &mut result[i * BLOCK_SIZE..i * BLOCK_SIZE + BLOCK_SIZE];
-		to_write.copy_from_slice(&ct);
-		block_add_one(&mut buff);
-	}
-
-	let enc = sm4::sm4_enc(&key, &buff);
-	for i in 0..tail_len {
-		let ii = block_count * 16 + i;
-		let b = input[ii] ^ enc[i];
-		result[ii] = b;
-	}
-
-	let result = result.to_vec();
-
-	Ok(result)
-}
-
-fn block_xor(a: &[u8], b: &[u8]) -> [u8; BLOCK_SIZE] {
-	let mut out: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
-	for i in 0..BLOCK_SIZE {
-		out[i] = a[i] ^ b[i];
-	}
-	out
-}
-
-fn block_add_one(a: &mut [u8]) {
-	let mut t;
-	let mut carry = 1;
-
-	for i in 0..16 {
-		t = i32::from(a[15 - i]) + carry;
-		if t == 256 {
-			t = 0;
-			carry = 1;
-		} else {
-			carry = 0
-		}
-		a[15 - i] = t as u8;
-	}
+fn to_key(key: &[u8]) -> Block {
+	let mut key_arr = [0u8; block_mode::BLOCK_SIZE];
+	key_arr.copy_from_slice(key);
+	key_arr
 }
 
 mod cases {
@@ -240,47 +348,332 @@ mod cases {
 		vec![
 			(
 				"sm4_enc",
-				vec![Case {
-					desc: "CTR".to_string(),
-					input: vec![
-						"-k",
-						"01010101010101010101010101010101",
-						"-i",
-						"03030303030303030303030303030303",
-						"-m",
-						"ctr",
-						"0x616263",
-					]
-					.into_iter()
-					.map(Into::into)
-					.collect(),
-					output: vec!["0x8cd7ea"].into_iter().map(Into::into).collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.6.0".to_string(),
-				}],
+				vec![
+					Case {
+						desc: "CTR".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-i",
+							"03030303030303030303030303030303",
+							"-m",
+							"ctr",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x8cd7ea"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.6.0".to_string(),
+					},
+					Case {
+						desc: "ECB (GB/T 32907 example 1)".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-m",
+							"ecb",
+							"-p",
+							"none",
+							"0x0123456789abcdeffedcba9876543210",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x681edf34d206965e86b3e94f536e4246"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ECB".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"0x616263",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0xd38534ec4ddef3e7843248f6419d142d"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "CBC".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"cbc",
+							"-p",
+							"none",
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x9554bcddf2d371452bffd93df8d461872360664050b1ae28e3e25ab2539ededb",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "CFB".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"cfb",
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xac3236cb970cc20791364c395a1342d12f1d1c833abb135086a6faa42f167242",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "OFB".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"ofb",
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xac3236cb970cc20791364c395a1342d13f238e807b4f96b1bc82314900fe35fd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "GCM (RFC 8998)".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-n",
+							"00001234567800000000abcd",
+							"-a",
+							"0xfeedfacedeadbeeffeedfacedeadbeefabaddad2",
+							"-m",
+							"gcm",
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccddddddddddddddddeeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0x17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d83de3541e4c2b58177e065a9bf7b62ec",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 			(
 				"sm4_dec",
-				vec![Case {
-					desc: "CTR".to_string(),
-					input: vec![
-						"-k",
-						"01010101010101010101010101010101",
-						"-i",
-						"03030303030303030303030303030303",
-						"-m",
-						"ctr",
-						"0x8cd7ea",
-					]
-					.into_iter()
-					.map(Into::into)
-					.collect(),
-					output: vec!["0x616263"].into_iter().map(Into::into).collect(),
-					is_example: true,
-					is_test: true,
-					since: "0.7.0".to_string(),
-				}],
+				vec![
+					Case {
+						desc: "CTR".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-i",
+							"03030303030303030303030303030303",
+							"-m",
+							"ctr",
+							"0x8cd7ea",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.7.0".to_string(),
+					},
+					Case {
+						desc: "ECB (GB/T 32907 example 1)".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-m",
+							"ecb",
+							"-p",
+							"none",
+							"0x681edf34d206965e86b3e94f536e4246",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x0123456789abcdeffedcba9876543210"]
+							.into_iter()
+							.map(Into::into)
+							.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "ECB".to_string(),
+						input: vec![
+							"-k",
+							"01010101010101010101010101010101",
+							"-m",
+							"ecb",
+							"0xd38534ec4ddef3e7843248f6419d142d",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec!["0x616263"].into_iter().map(Into::into).collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "CBC".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"cbc",
+							"-p",
+							"none",
+							"0x9554bcddf2d371452bffd93df8d461872360664050b1ae28e3e25ab2539ededb",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: true,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "CFB".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"cfb",
+							"0xac3236cb970cc20791364c395a1342d12f1d1c833abb135086a6faa42f167242",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "OFB".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-i",
+							"000102030405060708090a0b0c0d0e0f",
+							"-m",
+							"ofb",
+							"0xac3236cb970cc20791364c395a1342d13f238e807b4f96b1bc82314900fe35fd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+					Case {
+						desc: "GCM (RFC 8998)".to_string(),
+						input: vec![
+							"-k",
+							"0123456789abcdeffedcba9876543210",
+							"-n",
+							"00001234567800000000abcd",
+							"-a",
+							"0xfeedfacedeadbeeffeedfacedeadbeefabaddad2",
+							"-m",
+							"gcm",
+							"0x17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d83de3541e4c2b58177e065a9bf7b62ec",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						output: vec![
+							"0xaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccddddddddddddddddeeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa",
+						]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+						is_example: false,
+						is_test: true,
+						since: "0.13.0".to_string(),
+					},
+				],
 			),
 		]
 		.into_iter()
@@ -1,5 +1,5 @@
 use crate::modules::base::Hex;
-use crate::modules::{base, Command, Module};
+use crate::modules::{base, hash, Command, Module};
 use clap::{Arg, ArgMatches, SubCommand};
 
 mod sr25519;
@@ -48,7 +48,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Secret key (Private key, Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(base::in_format_arg())
+				.arg(base::out_format_arg()),
 			f: sr_sign,
 		},
 		Command {
@@ -75,7 +77,8 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Signature (Hex)")
 						.takes_value(true)
 						.required(true),
-				),
+				)
+				.arg(base::in_format_arg()),
 			f: sr_verify,
 		},
 		Command {
@@ -110,10 +113,41 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Secret key (Private key, Hex)")
 						.takes_value(true)
 						.required(false),
-				),
+				)
+				.arg(base::out_format_arg()),
 
 			f: sr_pk,
 		},
+		Command {
+			app: SubCommand::with_name("sr_derive")
+				.about("sr25519 derive key (Secret key, Public key)")
+				.arg(
+					Arg::with_name("MINI_SECRET_KEY")
+						.long("mini-secret-key")
+						.short("m")
+						.help("Mini secret key (Mini private key, Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SECRET_KEY")
+						.long("secret-key")
+						.short("s")
+						.help("Secret key (Private key, Hex)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PATH")
+						.long("path")
+						.short("p")
+						.help("Derivation path of Substrate junctions\n//: hard\n/: soft\ne.g. //polkadot//0/1\n")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(base::out_format_arg()),
+			f: sr_derive,
+		},
 	]
 }
 
@@ -187,6 +221,67 @@ fn sr_pk(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	Ok(vec![result])
 }
 
+fn sr_derive(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let secret_key = get_alt_secret_key(matches)?;
+
+	let path = matches.value_of("PATH").ok_or("Invalid path")?;
+	let junctions = parse_junctions(path)?;
+
+	let (secret_key, public_key) = sr25519::sr_derive_sr25519(secret_key, junctions)?;
+
+	let (secret_key, public_key): (String, String) =
+		(Hex::from(secret_key).into(), Hex::from(public_key).into());
+
+	let result = format!("({}, {})", secret_key, public_key);
+
+	Ok(vec![result])
+}
+
+/// Parse the path like //polkadot//0/1 into (chain code, hard) of each junction
+fn parse_junctions(path: &str) -> Result<Vec<([u8; 32], bool)>, String> {
+	let mut junctions = vec![];
+	let mut rest = path.trim();
+	while !rest.is_empty() {
+		let (hard, junction) = match (rest.strip_prefix("//"), rest.strip_prefix('/')) {
+			(Some(junction), _) => (true, junction),
+			(None, Some(junction)) => (false, junction),
+			_ => return Err("Invalid path: junction should start with / or //".to_string()),
+		};
+		let end = junction.find('/').unwrap_or(junction.len());
+		let (junction, remaining) = junction.split_at(end);
+		if junction.is_empty() {
+			return Err("Invalid path: empty junction".to_string());
+		}
+		junctions.push((junction_chain_code(junction), hard));
+		rest = remaining;
+	}
+	Ok(junctions)
+}
+
+/// SCALE encoded junction (u64 if numeric, otherwise string), blake2b-256 hashed if longer than 32 bytes
+fn junction_chain_code(junction: &str) -> [u8; 32] {
+	let encoded = match junction.parse::<u64>() {
+		Ok(number) => number.to_le_bytes().to_vec(),
+		Err(_) => [compact_length(junction.len()), junction.as_bytes().to_vec()].concat(),
+	};
+	let encoded = match encoded.len() > 32 {
+		true => hash::blake2b_hash(32, &encoded),
+		false => encoded,
+	};
+	let mut chain_code = [0u8; 32];
+	chain_code[..encoded.len()].copy_from_slice(&encoded);
+	chain_code
+}
+
+/// SCALE compact encoding of the length
+fn compact_length(len: usize) -> Vec<u8> {
+	match len {
+		0..=0x3f => vec![(len << 2) as u8],
+		0x40..=0x3fff => ((len << 2) as u16 | 0b01).to_le_bytes().to_vec(),
+		_ => ((len << 2) as u32 | 0b10).to_le_bytes().to_vec(),
+	}
+}
+
 fn get_alt_secret_key(matches: &ArgMatches) -> Result<AltSecretKey, String> {
 	if matches.is_present("MINI_SECRET_KEY") {
 		let secret_key = matches
@@ -235,4 +330,27 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_sr_derive_soft() {
+		let mini_secret_key = "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e"
+			.parse::<Hex>()
+			.unwrap()
+			.into();
+		let expected: Vec<u8> =
+			"0x96f56ff25557d90198ffdea4a317664140e7f33a3cb4ec5edc9640104ddbbd79"
+				.parse::<Hex>()
+				.unwrap()
+				.into();
+
+		let junctions = parse_junctions("//Alice/1").unwrap();
+		let (secret_key, public_key) =
+			sr25519::sr_derive_sr25519(AltSecretKey::MiniSecretKey(mini_secret_key), junctions)
+				.unwrap();
+
+		// the nonce of a soft derived secret key is random, but the key itself is not
+		assert_eq!(public_key, expected);
+		let public_key = sr25519::sr_pk_sr25519(AltSecretKey::SecretKey(secret_key)).unwrap();
+		assert_eq!(public_key, expected);
+	}
 }
@@ -2,6 +2,7 @@ use crate::modules::srdsa::AltSecretKey;
 use crate::modules::Case;
 use linked_hash_map::LinkedHashMap;
 use rand::thread_rng;
+use schnorrkel::derive::{ChainCode, Derivation};
 use schnorrkel::{ExpansionMode, Keypair};
 
 pub fn sr_gk_sr25519() -> Result<(Vec<u8>, Vec<u8>), String> {
@@ -63,6 +64,32 @@ pub fn sr_pk_sr25519(secret_key: AltSecretKey) -> Result<Vec<u8>, String> {
 	Ok(public_key)
 }
 
+pub fn sr_derive_sr25519(
+	secret_key: AltSecretKey,
+	junctions: Vec<([u8; 32], bool)>,
+) -> Result<(Vec<u8>, Vec<u8>), String> {
+	let key_pair = get_key_pair(secret_key)?;
+
+	let key_pair = junctions
+		.into_iter()
+		.fold(key_pair, |key_pair, (chain_code, hard)| {
+			let chain_code = ChainCode(chain_code);
+			match hard {
+				true => key_pair
+					.secret
+					.hard_derive_mini_secret_key(Some(chain_code), b"")
+					.0
+					.expand_to_keypair(ExpansionMode::Ed25519),
+				false => key_pair.derived_key_simple(chain_code, b"").0,
+			}
+		});
+
+	let secret_key = key_pair.secret.to_bytes().to_vec();
+	let public_key = key_pair.public.as_ref().to_vec();
+
+	Ok((secret_key, public_key))
+}
+
 fn get_key_pair(secret_key: AltSecretKey) -> Result<Keypair, String> {
 	let key_pair = match secret_key {
 		AltSecretKey::MiniSecretKey(key) => {
@@ -156,5 +183,24 @@ pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
                  since: "0.8.0".to_string(),
              },
          ]),
+        ("sr_derive",
+         vec![
+             Case {
+                 desc: "Hard junction (Alice of the Substrate dev seed)".to_string(),
+                 input: vec!["-m", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e", "-p", "//Alice"].into_iter().map(Into::into).collect(),
+                 output: vec!["(0x33a6f3093f158a7109f679410bef1a0c54168145e0cecb4df006c1c2fffb1f09925a225d97aa00682d6a59b95b18780c10d7032336e88f3442b42361f4a66011, 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d)"].into_iter().map(Into::into).collect(),
+                 is_example: true,
+                 is_test: true,
+                 since: "0.13.0".to_string(),
+             },
+             Case {
+                 desc: "Hard and soft junctions".to_string(),
+                 input: vec!["-m", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e", "-p", "//Alice/1"].into_iter().map(Into::into).collect(),
+                 output: vec!["(0x9f8955407e1d6e8dc455eb2f9fca8593ec386b94048689faada6cf933d746a0e19b2878aa29492c71a616442e4bfdebb05dc7027b5ccd633f86dbf70e902921b, 0x96f56ff25557d90198ffdea4a317664140e7f33a3cb4ec5edc9640104ddbbd79)"].into_iter().map(Into::into).collect(),
+                 is_example: true,
+                 is_test: false,
+                 since: "0.13.0".to_string(),
+             },
+         ]),
     ].into_iter().collect()
 }
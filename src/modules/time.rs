@@ -60,24 +60,35 @@ fn ts2d(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let timezone = matches.value_of("TIMEZONE");
 
+	let result = timestamp_to_date(timestamp, timezone)?;
+
+	Ok(vec![result])
+}
+
+pub fn timestamp_to_date(timestamp: i64, timezone: Option<&str>) -> Result<String, String> {
 	let result = match timezone {
 		Some(timezone) => {
 			let timezone: i32 = timezone.parse().map_err(|_| "Invalid input")?;
 			if timezone > 12 || timezone < -12 {
 				return Err("Invalid timezone".to_string());
 			}
-			FixedOffset::east(timezone * 3600)
-				.timestamp(timestamp, 0)
+			FixedOffset::east_opt(timezone * 3600)
+				.ok_or("Invalid timezone")?
+				.timestamp_opt(timestamp, 0)
+				.single()
+				.ok_or("Invalid timestamp")?
 				.format("%Y-%m-%d %H:%M:%S")
 				.to_string()
 		}
 		None => Local
-			.timestamp(timestamp, 0)
+			.timestamp_opt(timestamp, 0)
+			.single()
+			.ok_or("Invalid timestamp")?
 			.format("%Y-%m-%d %H:%M:%S")
 			.to_string(),
 	};
 
-	Ok(vec![result])
+	Ok(result)
 }
 
 fn d2ts(matches: &ArgMatches) -> Result<Vec<String>, String> {